@@ -0,0 +1,77 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{BatchVerifier, Signature, VerificationKey};
+
+fn signed_batch(n: usize) -> Vec<(VerificationKey, Signature)> {
+    let mut rng = rand::thread_rng();
+    (0..n)
+        .map(|_| {
+            let privkey = Scalar::random(&mut rng);
+            let pubkey = VerificationKey::from_secret(&privkey, &Scalar::random(&mut rng));
+            let sig = Signature::sign(&mut Transcript::new(b"bench"), pubkey, privkey);
+            (pubkey, sig)
+        })
+        .collect()
+}
+
+fn verify_single(c: &mut Criterion) {
+    let entries = signed_batch(1);
+    let (pubkey, sig) = entries[0];
+    c.bench_function("verify single", |b| {
+        b.iter(|| sig.verify(&mut Transcript::new(b"bench"), pubkey))
+    });
+}
+
+fn verify_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify batch");
+    for n in [16usize, 64, 256].iter() {
+        let entries = signed_batch(*n);
+        group.bench_with_input(BenchmarkId::from_parameter(n), &entries, |b, entries| {
+            b.iter(|| {
+                let mut batch = BatchVerifier::with_capacity(rand::thread_rng(), entries.len());
+                for (pubkey, sig) in entries.iter() {
+                    sig.verify_batched(&mut Transcript::new(b"bench"), *pubkey, &mut batch);
+                }
+                batch.verify()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn verify_batch_same_key(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let privkey = Scalar::random(&mut rng);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::random(&mut rng));
+
+    let mut group = c.benchmark_group("verify batch same key");
+    for n in [16usize, 64, 256].iter() {
+        let sigs: Vec<_> = (0..*n)
+            .map(|_| Signature::sign(&mut Transcript::new(b"bench"), pubkey, privkey))
+            .collect();
+        group.bench_with_input(BenchmarkId::new("separate", n), &sigs, |b, sigs| {
+            b.iter(|| {
+                let mut batch = BatchVerifier::with_capacity(rand::thread_rng(), sigs.len());
+                for sig in sigs.iter() {
+                    sig.verify_batched(&mut Transcript::new(b"bench"), pubkey, &mut batch);
+                }
+                batch.verify()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("combined", n), &sigs, |b, sigs| {
+            b.iter(|| {
+                let mut batch = BatchVerifier::with_capacity(rand::thread_rng(), sigs.len())
+                    .with_combined_points();
+                for sig in sigs.iter() {
+                    sig.verify_batched(&mut Transcript::new(b"bench"), pubkey, &mut batch);
+                }
+                batch.verify()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, verify_single, verify_batch, verify_batch_same_key);
+criterion_main!(benches);
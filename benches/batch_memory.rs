@@ -0,0 +1,66 @@
+//! Compares the peak heap usage of `BatchVerifier` with `verify_batch_lazy`.
+//! Criterion does not measure memory, so this counts allocations directly.
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use zkschnorr::{verify_batch_lazy, BatchVerifier, Signature, VerificationKey};
+
+struct CountingAlloc;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Returns the peak heap usage of `f` above the usage when it started.
+fn peak_usage<F: FnOnce() -> bool>(f: F) -> usize {
+    let start = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(start, Ordering::SeqCst);
+    assert!(f());
+    PEAK.load(Ordering::SeqCst) - start
+}
+
+fn main() {
+    let mut rng = rand::thread_rng();
+    for n in [256usize, 4096].iter() {
+        let entries: Vec<_> = (0..*n)
+            .map(|_| {
+                let privkey = Scalar::random(&mut rng);
+                let pubkey = VerificationKey::from_secret(&privkey, &Scalar::random(&mut rng));
+                let transcript = Transcript::new(b"bench");
+                let sig = Signature::sign(&mut transcript.clone(), pubkey, privkey);
+                (transcript, pubkey, sig)
+            })
+            .collect();
+
+        let buffered = peak_usage(|| {
+            let mut batch = BatchVerifier::with_capacity(rand::thread_rng(), entries.len());
+            for (transcript, pubkey, sig) in entries.iter() {
+                sig.verify_batched(&mut transcript.clone(), *pubkey, &mut batch);
+            }
+            batch.verify().is_ok()
+        });
+        let lazy = peak_usage(|| verify_batch_lazy(&entries, rand::thread_rng()).is_ok());
+
+        println!("batch of {:>5}: buffered {:>9} bytes, lazy {:>9} bytes", n, buffered, lazy);
+    }
+}
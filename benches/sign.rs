@@ -0,0 +1,52 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{Signature, VerificationKey};
+
+const HEADER: [&[u8]; 4] = [b"sender", b"recipient", b"protocol", b"channel"];
+
+fn append_header(t: &mut Transcript, field_len: usize) {
+    let value = vec![7u8; field_len];
+    for label in HEADER.iter() {
+        t.append_message(label, &value);
+    }
+}
+
+fn sign_with_prefix(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let privkey = Scalar::random(&mut rng);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::random(&mut rng));
+
+    let mut group = c.benchmark_group("sign shared header");
+    for field_len in [32usize, 1024].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("from scratch", field_len),
+            field_len,
+            |b, field_len| {
+                b.iter(|| {
+                    let mut t = Transcript::new(b"bench");
+                    append_header(&mut t, *field_len);
+                    t.append_message(b"amount", &10u64.to_le_bytes());
+                    Signature::sign(&mut t, pubkey, privkey)
+                })
+            },
+        );
+
+        let mut prefix = Transcript::new(b"bench");
+        append_header(&mut prefix, *field_len);
+        group.bench_with_input(BenchmarkId::new("with prefix", field_len), &prefix, |b, prefix| {
+            b.iter(|| {
+                Signature::sign_with_prefix(
+                    prefix,
+                    |t| t.append_message(b"amount", &10u64.to_le_bytes()),
+                    pubkey,
+                    privkey,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sign_with_prefix);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+//! Conformance checker for the message API.
+//!
+//! Reads a JSON file of `(pubkey, message, signature)` vectors in the format of
+//! `tests/message_vectors.json`, verifies each one with `Signature::verify_message`
+//! under the message label "message", and prints pass/fail per entry.
+//! Exits with a nonzero code if any vector fails.
+//!
+//! ```text
+//! cargo run --example verify_vectors -- tests/message_vectors.json
+//! ```
+
+use serde_json::Value;
+use std::process::exit;
+use zkschnorr::{Signature, VerificationKey, MESSAGE_TRANSCRIPT_LABEL};
+
+const MESSAGE_LABEL: &[u8] = b"message";
+const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/message_vectors.json");
+
+fn check(vector: &Value) -> Result<(), String> {
+    let field = |name: &str| {
+        vector[name]
+            .as_str()
+            .ok_or_else(|| format!("missing field `{}`", name))
+    };
+    let pubkey = hex::decode(field("pubkey")?).map_err(|e| format!("pubkey: {}", e))?;
+    let pubkey = VerificationKey::from_bytes(&pubkey).map_err(|e| format!("pubkey: {}", e))?;
+    let sig = hex::decode(field("signature")?).map_err(|e| format!("signature: {}", e))?;
+    let sig = Signature::from_bytes(&sig[..]).map_err(|e| format!("signature: {}", e))?;
+    sig.verify_message(MESSAGE_LABEL, field("message")?.as_bytes(), pubkey)
+        .map_err(|e| e.to_string())
+}
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_PATH.to_string());
+    let file: Value = match std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()))
+    {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("cannot read {}: {}", path, e);
+            exit(2);
+        }
+    };
+
+    if file["transcript_label"].as_str().map(str::as_bytes) != Some(MESSAGE_TRANSCRIPT_LABEL)
+        || file["message_label"].as_str().map(str::as_bytes) != Some(MESSAGE_LABEL)
+    {
+        eprintln!(
+            "{}: vectors must use transcript label {:?} and message label {:?}",
+            path,
+            String::from_utf8_lossy(MESSAGE_TRANSCRIPT_LABEL),
+            String::from_utf8_lossy(MESSAGE_LABEL),
+        );
+        exit(2);
+    }
+
+    let vectors = file["vectors"].as_array().cloned().unwrap_or_default();
+    let mut failures = 0;
+    for (i, vector) in vectors.iter().enumerate() {
+        match check(vector) {
+            Ok(()) => println!("vector {}: pass", i),
+            Err(e) => {
+                println!("vector {}: FAIL ({})", i, e);
+                failures += 1;
+            }
+        }
+    }
+    println!("{} of {} vectors passed", vectors.len() - failures, vectors.len());
+    if failures > 0 {
+        exit(1);
+    }
+}
@@ -0,0 +1,33 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::{ABSTAIN_LABEL, ABSTAIN_TOKEN};
+
+impl Signature {
+    /// Signs an explicit abstention in the context of `transcript`: the reserved
+    /// `ABSTAIN_TOKEN` (labelled `ABSTAIN_LABEL`) is appended to the transcript before
+    /// signing. The record proves that the key holder chose not to sign, and it does
+    /// not verify as an ordinary signature over the same transcript.
+    pub fn sign_abstain(
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        transcript.append_message(ABSTAIN_LABEL, ABSTAIN_TOKEN);
+        Self::sign(transcript, pubkey, privkey)
+    }
+
+    /// Verifies an abstention record created with `sign_abstain`.
+    /// Transcript should be in the same state as it was during the `sign_abstain` call.
+    pub fn verify_abstain(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        transcript.append_message(ABSTAIN_LABEL, ABSTAIN_TOKEN);
+        self.verify(transcript, pubkey)
+    }
+}
@@ -0,0 +1,55 @@
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::metrics::decompress;
+use super::transcript::{
+    TranscriptProtocol, G_LABEL, H_LABEL, KEY_AGGREGATION_LABEL, KEY_COEFFICIENT_LABEL,
+};
+
+/// Aggregates verification keys MuSig-style into `(Σ a_i·G_i, Σ a_i·H_i)`, returning the
+/// aggregate key together with each key and its coefficient `a_i`, for use as the weights
+/// of `Signature::verify_aggregate`.
+///
+/// The keys are first sorted by their canonical encoding, and every coefficient is
+/// squeezed from a transcript committing to the whole sorted list and then to the key
+/// itself. Aggregation is therefore independent of the input order: every participant
+/// derives the same aggregate key and coefficients from the same set of keys.
+/// The returned weights are in sorted order. Fails with `NoKeyShares` for empty input
+/// and `InvalidPoint` if a key does not decompress.
+///
+/// As with `VerificationKey::from_shares`, the aggregate key has a signing key only if
+/// all keys share the same `G`: with key secrets `x_i` it is `Σ a_i·x_i / Σ a_i`.
+pub fn aggregate_keys(
+    keys: &[VerificationKey],
+) -> Result<(VerificationKey, Vec<(VerificationKey, Scalar)>), ZkSchnorrError> {
+    if keys.is_empty() {
+        return Err(ZkSchnorrError::NoKeyShares);
+    }
+    let mut sorted = keys.to_vec();
+    sorted.sort();
+
+    let mut t = Transcript::new(KEY_AGGREGATION_LABEL);
+    t.append_u64(b"n", sorted.len() as u64);
+    for key in &sorted {
+        t.append_point(G_LABEL, &key.g);
+        t.append_point(H_LABEL, &key.h);
+    }
+
+    let mut g = RistrettoPoint::identity();
+    let mut h = RistrettoPoint::identity();
+    let mut weights = Vec::with_capacity(sorted.len());
+    for key in sorted {
+        let mut key_t = t.clone();
+        key_t.append_point(G_LABEL, &key.g);
+        key_t.append_point(H_LABEL, &key.h);
+        let a = key_t.challenge_scalar(KEY_COEFFICIENT_LABEL);
+        g += decompress(&key.g).ok_or(ZkSchnorrError::InvalidPoint)? * a;
+        h += decompress(&key.h).ok_or(ZkSchnorrError::InvalidPoint)? * a;
+        weights.push((key, a));
+    }
+    Ok((VerificationKey::from_compressed(g.compress(), h.compress()), weights))
+}
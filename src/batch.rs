@@ -1,17 +1,30 @@
 use core::borrow::Borrow;
 use core::iter;
+use core::ops::Range;
 use curve25519_dalek::ristretto::RistrettoPoint;
 use curve25519_dalek::scalar::Scalar;
 use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
 use rand_core::{CryptoRng, RngCore};
+use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::HashSet;
+#[cfg(feature = "timed")]
+use std::time::{Duration, Instant};
 
 use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::metrics::decompress;
+use super::signature::{compute_challenge, default_rng, Signature};
+use merlin::Transcript;
 
 /// Trait for a batch verification of signatures.
 /// If you are only verifying signatures, without other proofs, you can use
 /// concrete implementation `BatchVerifier` without rolling out your own.
 pub trait BatchVerification {
     /// Adds scalar for multiplying by a base point and pairs of dynamic scalars/points.
+    /// Every verification key carries its own base point, so `basepoint_scalar`
+    /// multiplies the first dynamic point (the key's `G` for a signature) and there is
+    /// one fewer dynamic scalar than dynamic points.
     /// The API admits variable-length iterators of scalars/points
     /// for compatibility with multi-key signatures (see Musig).
     /// It is responsibility of the caller to provide iterators of scalars and points with matching lengths.
@@ -20,11 +33,35 @@ pub trait BatchVerification {
         I: IntoIterator<Item = Scalar>,
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<RistrettoPoint>>;
+
+    /// Adds an arbitrary linear relation `0 == sum(scalars[i] * points[i])`, e.g. an
+    /// equation from another proof system, to be checked together with the signatures.
+    /// As with `append`, it is responsibility of the caller to provide iterators of
+    /// scalars and points with matching lengths. The batch weights the whole relation
+    /// with its own random factor, so the scalars should not be pre-weighted. An empty
+    /// relation holds trivially and appends nothing.
+    fn append_relation<I, J>(&mut self, scalars: I, points: J)
+    where
+        I: IntoIterator<Item = Scalar>,
+        J: IntoIterator<Item = RistrettoPoint>,
+    {
+        let mut scalars = scalars.into_iter();
+        if let Some(first) = scalars.next() {
+            self.append(first, scalars, points.into_iter().map(Some));
+        }
+    }
 }
 
 /// Single signature verifier that implements batching interface.
+///
+/// It serves code written against `BatchVerification`. To check one signature, use
+/// `Signature::verify`, which evaluates `verify_equation` over fixed-size arrays and
+/// builds no vectors of its own.
+///
+/// Every appended relation must hold: once one fails, the result stays an error.
 pub struct SingleVerifier {
     result: Result<(), ZkSchnorrError>,
+    appended: bool,
 }
 
 impl SingleVerifier {
@@ -34,10 +71,15 @@ impl SingleVerifier {
         F: FnOnce(&mut Self),
     {
         let mut verifier = Self {
-            result: Err(ZkSchnorrError::InvalidSignature),
+            result: Ok(()),
+            appended: false,
         };
         closure(&mut verifier);
-        verifier.result
+        if verifier.appended {
+            verifier.result
+        } else {
+            Err(ZkSchnorrError::InvalidSignature)
+        }
     }
 }
 
@@ -48,6 +90,10 @@ impl BatchVerification for SingleVerifier {
         I::Item: Borrow<Scalar>,
         J: IntoIterator<Item = Option<RistrettoPoint>>,
     {
+        self.appended = true;
+        if self.result.is_err() {
+            return;
+        }
         self.result = RistrettoPoint::optional_multiscalar_mul(
             iter::once(basepoint_scalar).chain(dynamic_scalars),
             dynamic_points,
@@ -63,12 +109,62 @@ impl BatchVerification for SingleVerifier {
     }
 }
 
+/// Heap bytes held per term of the pending multiscalar multiplication: a weight and a point.
+const TERM_BYTES: usize = WEIGHT_BYTES + POINT_BYTES;
+const WEIGHT_BYTES: usize = core::mem::size_of::<Scalar>();
+const POINT_BYTES: usize = core::mem::size_of::<Option<RistrettoPoint>>();
+/// Heap bytes held per entry index (entry starts and flagged entries).
+const INDEX_BYTES: usize = core::mem::size_of::<usize>();
+
+/// Multiscalar multiplication used by `BatchVerifier` for its final check,
+/// so that an optimized implementation (e.g. on a GPU) can be plugged in with
+/// `BatchVerifier::with_backend`. The default is `DalekBackend`.
+pub trait MultiscalarBackend {
+    /// Returns `sum(scalars[i] * points[i])`. Inputs are public, so the computation
+    /// may be variable-time. The slices have equal lengths.
+    fn vartime_multiscalar_mul(
+        &self,
+        scalars: &[Scalar],
+        points: &[RistrettoPoint],
+    ) -> RistrettoPoint;
+}
+
+/// The multiscalar multiplication of `curve25519-dalek`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DalekBackend;
+
+impl MultiscalarBackend for DalekBackend {
+    fn vartime_multiscalar_mul(
+        &self,
+        scalars: &[Scalar],
+        points: &[RistrettoPoint],
+    ) -> RistrettoPoint {
+        RistrettoPoint::vartime_multiscalar_mul(scalars, points)
+    }
+}
+
+/// Bounds on a batch set by `BatchVerifier::with_limits`.
+#[derive(Copy, Clone, Debug)]
+struct Limits {
+    entries: usize,
+    bytes: usize,
+}
+
 /// Batch signature verifier for use with `Signature::verify_batched`.
-pub struct BatchVerifier<R: RngCore + CryptoRng> {
+pub struct BatchVerifier<R: RngCore + CryptoRng, B: MultiscalarBackend = DalekBackend> {
     rng: R,
-    basepoint_scalar: Scalar,
+    backend: B,
+    short_weights: bool,
+    combine_points: bool,
+    limits: Option<Limits>,
+    limit_exceeded: bool,
+    entries: usize,
+    invalid_entries: Vec<usize>,
+    revoked_entries: Vec<usize>,
+    entry_starts: Vec<usize>,
     dyn_weights: Vec<Scalar>,
     dyn_points: Vec<Option<RistrettoPoint>>,
+    sealed_results: Vec<Result<(), ZkSchnorrError>>,
 }
 
 impl<R: RngCore + CryptoRng> BatchVerifier<R> {
@@ -79,31 +175,571 @@ impl<R: RngCore + CryptoRng> BatchVerifier<R> {
 
     /// Returns a new instance for batch verification with pre-allocated capacity `n`
     /// for verifying `n` simple schnorr signatures.
+    ///
+    /// The capacity is only a hint: if the number of terms overflows `usize` or
+    /// cannot be allocated, the verifier starts empty and grows as entries are appended.
     pub fn with_capacity(rng: R, capacity: usize) -> Self {
+        let terms = capacity.saturating_mul(3);
+        let mut dyn_weights = Vec::new();
+        let mut dyn_points = Vec::new();
+        if dyn_weights.try_reserve_exact(terms).is_err()
+            || dyn_points.try_reserve_exact(terms).is_err()
+        {
+            dyn_weights = Vec::new();
+            dyn_points = Vec::new();
+        }
         Self {
             rng,
-            basepoint_scalar: Scalar::zero(),
-            dyn_weights: Vec::with_capacity(capacity * 2),
-            dyn_points: Vec::with_capacity(capacity * 2),
+            backend: DalekBackend,
+            short_weights: false,
+            combine_points: false,
+            limits: None,
+            limit_exceeded: false,
+            entries: 0,
+            invalid_entries: Vec::new(),
+            revoked_entries: Vec::new(),
+            entry_starts: Vec::new(),
+            dyn_weights,
+            dyn_points,
+            sealed_results: Vec::new(),
         }
     }
 
+    /// Returns a new instance for batch verification that holds at most `max_entries`
+    /// entries and `max_bytes` bytes of buffers (see `memory_usage`), protecting a
+    /// verifier of untrusted input from unbounded growth. Buffers grow only after an
+    /// entry is checked against the limits.
+    ///
+    /// `append_signature` and `append_checked` fail with `BatchTooLarge` when an entry
+    /// would exceed a limit, leaving the batch untouched. An entry appended past a limit
+    /// through `BatchVerification::append` is dropped instead, and `verify` then fails
+    /// with `BatchTooLarge`.
+    pub fn with_limits(rng: R, max_entries: usize, max_bytes: usize) -> Self {
+        let mut verifier = Self::new(rng);
+        verifier.limits = Some(Limits {
+            entries: max_entries,
+            bytes: max_bytes,
+        });
+        verifier
+    }
+
+    /// Returns a verifier holding the entries of all `parts`, e.g. built by separate
+    /// threads, which are verified together; `rng` weights entries appended afterwards.
+    /// Entry indices (as reported by `validate_keys`, `RevokedKeys` or
+    /// `verify_bitmap`) continue across parts in iteration order.
+    ///
+    /// The entries are merged as already weighted, without re-randomizing them, so the
+    /// parts must have been weighted independently: each with its own cryptographic RNG,
+    /// not with RNGs seeded identically or predictably. A part whose limits were
+    /// exceeded makes the merged verifier fail with `BatchTooLarge`. If every part was
+    /// created with `with_limits`, the merged verifier is limited to the sums of their
+    /// limits. Results sealed by the parts are not carried over.
+    pub fn from_parts<R2, B2>(
+        rng: R,
+        parts: impl IntoIterator<Item = BatchVerifier<R2, B2>>,
+    ) -> Self
+    where
+        R2: RngCore + CryptoRng,
+        B2: MultiscalarBackend,
+    {
+        let mut verifier = Self::new(rng);
+        let mut limits = Some(Limits {
+            entries: 0,
+            bytes: 0,
+        });
+        let mut merged = false;
+        for part in parts {
+            merged = true;
+            limits = limits.zip(part.limits).map(|(sum, part)| Limits {
+                entries: sum.entries.saturating_add(part.entries),
+                bytes: sum.bytes.saturating_add(part.bytes),
+            });
+            let offset = verifier.dyn_points.len();
+            let entries = verifier.entries;
+            verifier.limit_exceeded |= part.limit_exceeded;
+            verifier.invalid_entries.extend(part.invalid_entries.iter().map(|i| i + entries));
+            verifier.revoked_entries.extend(part.revoked_entries.iter().map(|i| i + entries));
+            verifier.entry_starts.extend(part.entry_starts.iter().map(|s| s + offset));
+            verifier.dyn_weights.extend(part.dyn_weights);
+            verifier.dyn_points.extend(part.dyn_points);
+            verifier.entries += part.entries;
+        }
+        if merged {
+            verifier.limits = limits;
+        }
+        verifier
+    }
+}
+
+impl<R: RngCore + CryptoRng, B: MultiscalarBackend> BatchVerifier<R, B> {
+    /// Replaces the multiscalar multiplication used by the final check with `backend`.
+    pub fn with_backend<C: MultiscalarBackend>(self, backend: C) -> BatchVerifier<R, C> {
+        BatchVerifier {
+            rng: self.rng,
+            backend,
+            short_weights: self.short_weights,
+            combine_points: self.combine_points,
+            limits: self.limits,
+            limit_exceeded: self.limit_exceeded,
+            entries: self.entries,
+            invalid_entries: self.invalid_entries,
+            revoked_entries: self.revoked_entries,
+            entry_starts: self.entry_starts,
+            dyn_weights: self.dyn_weights,
+            dyn_points: self.dyn_points,
+            sealed_results: self.sealed_results,
+        }
+    }
+
+    /// Returns the number of heap bytes allocated for the entries appended so far,
+    /// including spare capacity: the weights, the points and the per-entry indices.
+    /// This is the quantity bounded by `with_limits`.
+    pub fn memory_usage(&self) -> usize {
+        buffer_bytes(
+            self.dyn_weights.capacity(),
+            self.dyn_points.capacity(),
+            self.entry_starts.capacity()
+                + self.invalid_entries.capacity()
+                + self.revoked_entries.capacity(),
+        )
+    }
+
+    /// Returns `true` if an unflagged entry of `terms` terms fits within the limits.
+    fn has_room(&self, terms: usize) -> bool {
+        self.entries < self.max_entries() && self.planned_capacities(terms, terms, 0).is_some()
+    }
+
+    /// Returns the entry limit, or `usize::MAX` if the batch is unlimited.
+    fn max_entries(&self) -> usize {
+        self.limits.map_or(usize::MAX, |limits| limits.entries)
+    }
+
+    /// Returns the byte limit, or `usize::MAX` if the batch is unlimited.
+    fn max_bytes(&self) -> usize {
+        self.limits.map_or(usize::MAX, |limits| limits.bytes)
+    }
+
+    /// Returns the capacities of the weights, points and entry starts after growing
+    /// them for an entry with `weights` weights and `points` points, whose flagging
+    /// grows the lists of flagged entries by `flag_slots`, or `None` if that exceeds
+    /// `max_bytes`. Buffers double like `Vec` while that fits, and grow exactly to the
+    /// required size otherwise.
+    fn planned_capacities(
+        &self,
+        weights: usize,
+        points: usize,
+        flag_slots: usize,
+    ) -> Option<[usize; 3]> {
+        let flags = self.invalid_entries.capacity() + self.revoked_entries.capacity() + flag_slots;
+        [false, true].iter().find_map(|&exact| {
+            let planned = [
+                grown_capacity(&self.dyn_weights, weights, exact),
+                grown_capacity(&self.dyn_points, points, exact),
+                grown_capacity(&self.entry_starts, 1, exact),
+            ];
+            let bytes = buffer_bytes(planned[0], planned[1], planned[2] + flags);
+            (bytes <= self.max_bytes()).then_some(planned)
+        })
+    }
+
+    /// Grows the buffers for an entry like `planned_capacities`, returning `false`
+    /// without allocating if it does not fit within the limits.
+    fn reserve(&mut self, weights: usize, points: usize, flag_slots: usize) -> bool {
+        if self.entries >= self.max_entries() {
+            return false;
+        }
+        match self.planned_capacities(weights, points, flag_slots) {
+            Some([weights, points, starts]) => {
+                reserve_to(&mut self.dyn_weights, weights);
+                reserve_to(&mut self.dyn_points, points);
+                reserve_to(&mut self.entry_starts, starts);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of slots `flags` grows by when an entry is flagged.
+    fn flag_slots(flags: &Vec<usize>) -> usize {
+        grown_capacity(flags, 1, false) - flags.capacity()
+    }
+
+    /// Records a flagged entry index, growing `flags` by `flag_slots`.
+    fn flag(flags: &mut Vec<usize>, index: usize) {
+        reserve_to(flags, grown_capacity(flags, 1, false));
+        flags.push(index);
+    }
+
+    /// Switches the verifier to 128-bit random weights instead of full-size scalars.
+    ///
+    /// With 128-bit weights, a batch containing an invalid entry is accepted with
+    /// probability at most about 2^-128 (instead of 2^-252), which matches the
+    /// 128-bit security level of the group. The saving is in sampling the weights:
+    /// each weight needs 16 random bytes instead of 64 and no wide reduction.
+    /// Note that the multiscalar multiplication itself still processes full-width
+    /// scalars, since the weights are multiplied into the signature scalars.
+    pub fn with_short_weights(mut self) -> Self {
+        self.short_weights = true;
+        self
+    }
+
+    /// Sums the weights of repeated points, e.g. of entries signed with the same key,
+    /// before the multiscalar multiplication, so that each distinct point is multiplied
+    /// once. Finding the repeats hashes every point of the batch, which only pays off
+    /// when keys do repeat, so this is off by default.
+    pub fn with_combined_points(mut self) -> Self {
+        self.combine_points = true;
+        self
+    }
+
+    fn random_weight(&mut self) -> Scalar {
+        if self.short_weights {
+            let mut bytes = [0u8; 32];
+            self.rng.fill_bytes(&mut bytes[..16]);
+            Scalar::from_bits(bytes)
+        } else {
+            Scalar::random(&mut self.rng)
+        }
+    }
+
+    /// Weights an entry's scalars with a fresh random factor and adds its terms,
+    /// flagging the entry if a point failed to decompress.
+    fn push_entry(
+        &mut self,
+        scalars: impl Iterator<Item = Scalar>,
+        points: impl Iterator<Item = Option<RistrettoPoint>>,
+    ) {
+        let r = self.random_weight();
+        let start = self.dyn_points.len();
+        let mut invalid = false;
+        self.dyn_weights.extend(scalars.map(|f| r * f));
+        self.dyn_points
+            .extend(points.inspect(|point| invalid |= point.is_none()));
+        self.entry_starts.push(start);
+        if invalid {
+            Self::flag(&mut self.invalid_entries, self.entries);
+        }
+        self.entries += 1;
+    }
+
+    /// Adds a borrowed signature to the batch, to be verified against `pubkey`.
+    /// This is equivalent to `signature.verify_batched(transcript, pubkey, self)`, which
+    /// appends the weighted relation `0 == -s·G + R + c·H` for the signature.
+    /// Transcript should be in the same state as it was during the `sign` call
+    /// that created the signature.
+    ///
+    /// Fails with `DegenerateSignature`, leaving the batch and transcript untouched,
+    /// if `R` is the identity or `s` is zero, and likewise with `BatchTooLarge` if the
+    /// entry would exceed the limits set by `with_limits`.
+    pub fn append_signature(
+        &mut self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        signature: &Signature,
+    ) -> Result<(), ZkSchnorrError> {
+        if signature.is_degenerate() {
+            return Err(ZkSchnorrError::DegenerateSignature);
+        }
+        if !self.has_room(3) {
+            return Err(ZkSchnorrError::BatchTooLarge);
+        }
+        signature.verify_batched(transcript, pubkey, self);
+        Ok(())
+    }
+
+    /// Adds a signature like `append_signature`, unless `pubkey` is in the `revoked`
+    /// set of encoded keys (see `VerificationKey::to_bytes_array`). A revoked entry is
+    /// skipped before any transcript or point work and flagged instead, so that
+    /// `verify` fails with `RevokedKeys` listing the indices, in append order,
+    /// of all flagged entries.
+    pub fn append_checked(
+        &mut self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        signature: &Signature,
+        revoked: &HashSet<[u8; 64]>,
+    ) -> Result<(), ZkSchnorrError> {
+        if revoked.contains(&pubkey.to_bytes_array()) {
+            if !self.reserve(0, 0, Self::flag_slots(&self.revoked_entries)) {
+                return Err(ZkSchnorrError::BatchTooLarge);
+            }
+            Self::flag(&mut self.revoked_entries, self.entries);
+            self.entry_starts.push(self.dyn_points.len());
+            self.entries += 1;
+            return Ok(());
+        }
+        self.append_signature(transcript, pubkey, signature)
+    }
+
     /// Performs the verification and returns the result.
+    /// The result does not depend on the order in which entries were appended.
+    /// Fails with `RevokedKeys` without any multiplication if entries were
+    /// flagged by `append_checked`, and with `BatchTooLarge` if an entry was
+    /// dropped for exceeding the limits set by `with_limits`.
     pub fn verify(self) -> Result<(), ZkSchnorrError> {
-        let result = RistrettoPoint::optional_multiscalar_mul(
-            iter::once(self.basepoint_scalar).chain(self.dyn_weights.into_iter()),
-            self.dyn_points.into_iter(),
-        )
-        .ok_or(ZkSchnorrError::InvalidBatch)?;
-        if result.is_identity() {
+        if self.limit_exceeded {
+            return Err(ZkSchnorrError::BatchTooLarge);
+        }
+        if !self.revoked_entries.is_empty() {
+            return Err(ZkSchnorrError::RevokedKeys(self.revoked_entries));
+        }
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(ZkSchnorrError::InvalidBatch)
+        }
+    }
+
+    /// Performs the verification like `verify`, also returning the time spent in the
+    /// multiscalar multiplication. The timing covers only that cryptographic operation,
+    /// not combining repeated points (see `with_combined_points`); it is zero if a point
+    /// failed to decompress.
+    #[cfg(feature = "timed")]
+    pub fn verify_timed(self) -> (Result<(), ZkSchnorrError>, Duration) {
+        if self.limit_exceeded {
+            return (Err(ZkSchnorrError::BatchTooLarge), Duration::default());
+        }
+        if !self.revoked_entries.is_empty() {
+            let err = ZkSchnorrError::RevokedKeys(self.revoked_entries);
+            return (Err(err), Duration::default());
+        }
+        let (weights, points) = match self.terms(0..self.dyn_points.len()) {
+            Some(terms) => terms,
+            None => return (Err(ZkSchnorrError::InvalidBatch), Duration::default()),
+        };
+        let start = Instant::now();
+        let valid = self.backend.vartime_multiscalar_mul(&weights, &points).is_identity();
+        let elapsed = start.elapsed();
+        if valid {
+            (Ok(()), elapsed)
+        } else {
+            (Err(ZkSchnorrError::InvalidBatch), elapsed)
+        }
+    }
+
+    /// Performs the verification like `verify`, but first rejects batches holding more
+    /// than `max_entries` entries with `BatchTooLarge`, before any expensive work is done.
+    /// This bounds the cost of verifying a batch assembled from untrusted input.
+    pub fn verify_with_limit(self, max_entries: usize) -> Result<(), ZkSchnorrError> {
+        if self.entries > max_entries {
+            return Err(ZkSchnorrError::BatchTooLarge);
+        }
+        self.verify()
+    }
+
+    /// Verifies the entries appended so far and clears them, keeping the allocated
+    /// capacity, so the same verifier can check the next round of entries.
+    /// The entries are cleared whether or not they verify, and the result is also
+    /// recorded in `sealed_results`.
+    pub fn seal(&mut self) -> Result<(), ZkSchnorrError> {
+        let result = if self.limit_exceeded {
+            Err(ZkSchnorrError::BatchTooLarge)
+        } else if !self.revoked_entries.is_empty() {
+            Err(ZkSchnorrError::RevokedKeys(core::mem::take(&mut self.revoked_entries)))
+        } else if self.is_valid() {
             Ok(())
         } else {
             Err(ZkSchnorrError::InvalidBatch)
+        };
+        self.entries = 0;
+        self.limit_exceeded = false;
+        self.invalid_entries.clear();
+        self.revoked_entries.clear();
+        self.entry_starts.clear();
+        self.dyn_weights.clear();
+        self.dyn_points.clear();
+        self.sealed_results.push(result.clone());
+        result
+    }
+
+    /// Returns the results of all rounds sealed so far, in sealing order.
+    /// The log grows by one result per `seal`.
+    pub fn sealed_results(&self) -> &[Result<(), ZkSchnorrError>] {
+        &self.sealed_results
+    }
+
+    /// Returns, for each entry in append order, whether it verifies on its own.
+    ///
+    /// The batch is checked as a whole first, and failing halves are bisected
+    /// recursively, reusing the weights already drawn for the entries. When most
+    /// entries are valid this costs a few multiscalar multiplications over the batch
+    /// instead of one verification per entry. Entries with a malformed point or
+    /// flagged by `append_checked` are reported as invalid.
+    ///
+    /// Fails with `BatchTooLarge` if an entry was dropped for exceeding the limits set
+    /// by `with_limits`, since the bitmap would not line up with the appended entries.
+    pub fn verify_bitmap(self) -> Result<Vec<bool>, ZkSchnorrError> {
+        if self.limit_exceeded {
+            return Err(ZkSchnorrError::BatchTooLarge);
+        }
+        let mut bitmap = vec![false; self.entries];
+        self.bisect(0..self.entries, &mut bitmap);
+        Ok(bitmap)
+    }
+
+    fn bisect(&self, entries: Range<usize>, bitmap: &mut [bool]) {
+        if entries.is_empty() {
+            return;
+        }
+        if self.entries_are_valid(entries.clone()) {
+            bitmap[entries].iter_mut().for_each(|valid| *valid = true);
+        } else if entries.len() > 1 {
+            let mid = entries.start + entries.len() / 2;
+            self.bisect(entries.start..mid, bitmap);
+            self.bisect(mid..entries.end, bitmap);
+        }
+    }
+
+    /// Checks the relations of a contiguous range of entries.
+    fn entries_are_valid(&self, entries: Range<usize>) -> bool {
+        if self.revoked_entries.iter().any(|i| entries.contains(i)) {
+            return false;
+        }
+        let start = self.entry_starts[entries.start];
+        let end = self
+            .entry_starts
+            .get(entries.end)
+            .copied()
+            .unwrap_or(self.dyn_points.len());
+        match self.terms(start..end) {
+            Some((weights, points)) => {
+                self.backend.vartime_multiscalar_mul(&weights, &points).is_identity()
+            }
+            None => false,
         }
     }
+
+    /// Returns the indices, in append order, of the entries with a point that failed
+    /// to decompress (a malformed key or `R`, or a degenerate signature, whose `R` is
+    /// appended as malformed), without running the multiscalar
+    /// multiplication. A batch with any such entry fails to verify; an empty result
+    /// means that any failure of `verify` comes from an invalid signature.
+    pub fn validate_keys(&self) -> Vec<usize> {
+        self.invalid_entries.clone()
+    }
+
+    /// Checks the accumulated batch without consuming the verifier,
+    /// so the entries can still be inspected if the check fails.
+    ///
+    /// This runs the same multiscalar multiplication as `verify`. The points are
+    /// copied out of the batch, which costs an allocation proportional to the number
+    /// of points (of distinct points with `with_combined_points`).
+    pub fn is_valid(&self) -> bool {
+        if self.limit_exceeded || !self.revoked_entries.is_empty() {
+            return false;
+        }
+        match self.terms(0..self.dyn_points.len()) {
+            Some((weights, points)) => {
+                self.backend.vartime_multiscalar_mul(&weights, &points).is_identity()
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of points in the pending multiscalar multiplication,
+    /// after repeated points are combined if `with_combined_points` is set, as a
+    /// proxy for the cost of `verify`. A batch containing an invalid point fails
+    /// before any multiplication, so its cost is 0.
+    ///
+    /// This performs the point deduplication, but no scalar multiplications.
+    pub fn estimated_cost(&self) -> usize {
+        self.terms(0..self.dyn_points.len())
+            .map(|(_, points)| points.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns the weights and points of the multiscalar multiplication over the
+    /// given range of terms, with repeated points combined if `with_combined_points`
+    /// is set. Returns `None` if any point failed to decompress.
+    fn terms(&self, terms: Range<usize>) -> Option<(Cow<'_, [Scalar]>, Vec<RistrettoPoint>)> {
+        let weights = &self.dyn_weights[terms.clone()];
+        let points = &self.dyn_points[terms];
+        if self.combine_points {
+            let (weights, points) = combine_repeated_points(weights, points)?;
+            Some((Cow::Owned(weights), points))
+        } else {
+            let points = points.iter().copied().collect::<Option<Vec<_>>>()?;
+            Some((Cow::Borrowed(weights), points))
+        }
+    }
+}
+
+/// Returns the heap bytes of buffers with the given capacities of weights, points and indices.
+fn buffer_bytes(weights: usize, points: usize, indices: usize) -> usize {
+    weights
+        .saturating_mul(WEIGHT_BYTES)
+        .saturating_add(points.saturating_mul(POINT_BYTES))
+        .saturating_add(indices.saturating_mul(INDEX_BYTES))
+}
+
+/// Returns the capacity `buffer` needs to hold `additional` more elements: unchanged if
+/// they fit, otherwise the required size, or at least double the capacity unless `exact`.
+fn grown_capacity<T>(buffer: &Vec<T>, additional: usize, exact: bool) -> usize {
+    let required = buffer.len().saturating_add(additional);
+    if required <= buffer.capacity() || exact {
+        required.max(buffer.capacity())
+    } else {
+        required.max(buffer.capacity().saturating_mul(2))
+    }
 }
 
-impl<R: RngCore + CryptoRng> BatchVerification for BatchVerifier<R> {
+/// Grows `buffer` to at least `capacity` elements.
+fn reserve_to<T>(buffer: &mut Vec<T>, capacity: usize) {
+    buffer.reserve_exact(capacity.saturating_sub(buffer.len()));
+}
+
+/// Sums the weights of identical points, so that entries sharing a key
+/// (the same `g` and `h`) contribute each point to the multiscalar
+/// multiplication only once. Returns `None` if any point failed to decompress.
+fn combine_repeated_points(
+    weights: &[Scalar],
+    points: &[Option<RistrettoPoint>],
+) -> Option<(Vec<Scalar>, Vec<RistrettoPoint>)> {
+    let points = points.iter().copied().collect::<Option<Vec<_>>>()?;
+
+    // Points are compared by the encoding of their double, which is injective
+    // in the prime-order group and is computed with a single batched inversion.
+    let encodings = RistrettoPoint::double_and_compress_batch(&points);
+    let mut positions: HashMap<[u8; 32], usize> = HashMap::with_capacity(points.len());
+    let mut unique_weights = Vec::with_capacity(points.len());
+    let mut unique_points = Vec::with_capacity(points.len());
+
+    for ((weight, point), encoding) in weights.iter().zip(points).zip(encodings) {
+        match positions.entry(encoding.to_bytes()) {
+            Entry::Occupied(e) => unique_weights[*e.get()] += weight,
+            Entry::Vacant(e) => {
+                e.insert(unique_points.len());
+                unique_weights.push(*weight);
+                unique_points.push(point);
+            }
+        }
+    }
+    Some((unique_weights, unique_points))
+}
+
+#[cfg(feature = "tokio")]
+impl<R, B> BatchVerifier<R, B>
+where
+    R: RngCore + CryptoRng + Send + 'static,
+    B: MultiscalarBackend + Send + 'static,
+{
+    /// Performs the verification like `verify` on Tokio's blocking thread pool.
+    ///
+    /// The batch is moved to a thread from `tokio::task::spawn_blocking`, so the
+    /// multiscalar multiplication does not stall the async executor; the returned
+    /// future completes when that thread is done. It must be awaited within a Tokio
+    /// runtime. The RNG and backend must be `Send`, so e.g. `rand::rngs::OsRng`
+    /// works but the thread-local RNG does not. A panic during verification is
+    /// propagated to the awaiting task.
+    pub async fn verify_blocking(self) -> Result<(), ZkSchnorrError> {
+        match tokio::task::spawn_blocking(move || self.verify()).await {
+            Ok(result) => result,
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng, B: MultiscalarBackend> BatchVerification for BatchVerifier<R, B> {
     fn append<I, J>(&mut self, basepoint_scalar: I::Item, dynamic_scalars: I, dynamic_points: J)
     where
         I: IntoIterator,
@@ -111,12 +747,276 @@ impl<R: RngCore + CryptoRng> BatchVerification for BatchVerifier<R> {
         J: IntoIterator<Item = Option<RistrettoPoint>>,
     {
         // Random factor `r` for each set of operations guarantees that
-        // individual operations are unlikely (p < 2^-252) to cancel each other,
+        // individual operations are unlikely (p < 2^-252, or 2^-128 with
+        // short weights) to cancel each other,
         // and therefore each operation must produce an identity point.
-        let r = Scalar::random(&mut self.rng);
-        self.basepoint_scalar += r * basepoint_scalar.borrow();
-        self.dyn_weights
-            .extend(dynamic_scalars.into_iter().map(|f| r * f.borrow()));
-        self.dyn_points.extend(dynamic_points);
+        // Each entry carries its own generator `pk.g` as the first dynamic point,
+        // so the basepoint scalar is weighted per entry rather than accumulated.
+        if self.limits.is_none() {
+            self.push_entry(
+                iter::once(basepoint_scalar)
+                    .chain(dynamic_scalars)
+                    .map(|f| *f.borrow()),
+                dynamic_points.into_iter(),
+            );
+            return;
+        }
+        // With limits, the entry is collected before the batch grows, so that an entry
+        // exceeding them is dropped without allocating more than `max_bytes` for it.
+        if self.entries >= self.max_entries() {
+            self.limit_exceeded = true;
+            return;
+        }
+        let max_terms = (self.max_bytes() / TERM_BYTES).saturating_add(1);
+        let scalars: Vec<Scalar> = iter::once(basepoint_scalar)
+            .chain(dynamic_scalars)
+            .take(max_terms)
+            .map(|f| *f.borrow())
+            .collect();
+        let points: Vec<Option<RistrettoPoint>> =
+            dynamic_points.into_iter().take(max_terms).collect();
+        let flag_slots = if points.iter().any(Option::is_none) {
+            Self::flag_slots(&self.invalid_entries)
+        } else {
+            0
+        };
+        if !self.reserve(scalars.len(), points.len(), flag_slots) {
+            self.limit_exceeded = true;
+            return;
+        }
+        self.push_entry(scalars.into_iter(), points.into_iter());
+    }
+}
+
+/// Verifier facade that checks any number of signatures behind one API.
+///
+/// A single signature is checked directly, without an RNG (like `Signature::verify`);
+/// two or more are checked together with a `BatchVerifier`, which needs an RNG for
+/// its random weights. This lets call sites that only sometimes batch use one code path.
+#[derive(Default)]
+pub struct Verifier {
+    entries: Vec<(Signature, Transcript, VerificationKey)>,
+}
+
+impl Verifier {
+    /// Returns a new empty verifier
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a signature to be verified against `pubkey`.
+    /// Transcript should be in the same state as it was during the `sign` call
+    /// that created the signature.
+    ///
+    /// Fails with `DegenerateSignature` if `R` is the identity or `s` is zero.
+    pub fn push(
+        &mut self,
+        signature: Signature,
+        transcript: Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        if signature.is_degenerate() {
+            return Err(ZkSchnorrError::DegenerateSignature);
+        }
+        self.entries.push((signature, transcript, pubkey));
+        Ok(())
+    }
+
+    /// Returns the number of signatures added so far.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no signatures were added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Verifies all signatures, using the default signing RNG if a batch is needed.
+    /// Fails with `InvalidSignature` for a single signature and `InvalidBatch` otherwise.
+    pub fn verify(self) -> Result<(), ZkSchnorrError> {
+        self.verify_with_rng(default_rng)
+    }
+
+    /// Verifies all signatures, calling `rng` to create an RNG only if there are
+    /// two or more signatures to batch.
+    pub fn verify_with_rng<T, F>(self, rng: F) -> Result<(), ZkSchnorrError>
+    where
+        T: RngCore + CryptoRng,
+        F: FnOnce() -> T,
+    {
+        let mut entries = self.entries;
+        match entries.len() {
+            0 => Ok(()),
+            1 => {
+                let (signature, mut transcript, pubkey) = entries.remove(0);
+                signature.verify(&mut transcript, pubkey)
+            }
+            n => {
+                let mut batch = BatchVerifier::with_capacity(rng(), n);
+                for (signature, mut transcript, pubkey) in entries {
+                    signature.verify_batched(&mut transcript, pubkey, &mut batch);
+                }
+                batch.verify()
+            }
+        }
+    }
+}
+
+/// Verifies a stream of signatures in sub-batches of up to `batch_size` entries
+/// (at least one), stopping at the first sub-batch that fails.
+/// Each sub-batch is checked like `Verifier::verify`, so a final sub-batch of one
+/// signature fails with `InvalidSignature` and larger ones with `InvalidBatch`.
+/// Degenerate signatures fail with `DegenerateSignature` as soon as they are read.
+pub fn verify_stream<I>(items: I, batch_size: usize) -> Result<(), ZkSchnorrError>
+where
+    I: IntoIterator<Item = (Transcript, VerificationKey, Signature)>,
+{
+    let batch_size = core::cmp::max(batch_size, 1);
+    let mut verifier = Verifier::new();
+    for (transcript, pubkey, signature) in items {
+        verifier.push(signature, transcript, pubkey)?;
+        if verifier.len() == batch_size {
+            core::mem::take(&mut verifier).verify()?;
+        }
+    }
+    verifier.verify()
+}
+
+/// Verifies a one-shot batch of signatures directly from borrowed entries.
+///
+/// Unlike `BatchVerifier`, which decompresses each key and `R` when the entry is
+/// appended and keeps the weights and points until `verify`, this walks `entries`
+/// twice: once to derive the weighted scalars, and once to decompress the points,
+/// and hands both to the multiscalar multiplication as iterators. This function keeps
+/// no buffers of its own, but the multiplication in `curve25519-dalek` collects all
+/// scalars and a lookup table per point before it starts, so memory still grows with
+/// the number of entries, as with `BatchVerifier`. Repeated keys are not combined.
+///
+/// Transcripts should be in the same state as they were during the `sign` calls;
+/// they are cloned, not modified. Fails with `DegenerateSignature` if any signature
+/// is degenerate, and with `InvalidBatch` if any point fails to decompress or
+/// any signature is invalid.
+pub fn verify_batch_lazy<'a, I, R>(entries: I, mut rng: R) -> Result<(), ZkSchnorrError>
+where
+    I: IntoIterator<Item = &'a (Transcript, VerificationKey, Signature)> + Clone,
+    R: RngCore + CryptoRng,
+{
+    if entries.clone().into_iter().any(|(_, _, sig)| sig.is_degenerate()) {
+        return Err(ZkSchnorrError::DegenerateSignature);
+    }
+
+    // Same relation as `Signature::verify_batched`, weighted per entry:
+    // `0 == w * ((-s * pk.g) + (1 * R) + (c * pk.h))`
+    let scalars = entries.clone().into_iter().flat_map(|(transcript, pubkey, sig)| {
+        let c = compute_challenge(&mut transcript.clone(), pubkey, &sig.R);
+        let w = Scalar::random(&mut rng);
+        [-(w * sig.s), w, w * c]
+    });
+    let points = entries
+        .into_iter()
+        .flat_map(|(_, pubkey, sig)| [pubkey.g, sig.R, pubkey.h])
+        .map(|point| decompress(&point));
+
+    match RistrettoPoint::optional_multiscalar_mul(scalars, points) {
+        Some(result) if result.is_identity() => Ok(()),
+        _ => Err(ZkSchnorrError::InvalidBatch),
+    }
+}
+
+/// Returns the soundness of batch verification in bits: an invalid batch of
+/// `num_entries` entries passes with probability at most `2^-bits` when each entry
+/// is weighted with an independent uniformly random `weight_bits`-bit weight.
+///
+/// In the prime-order Ristretto group, for any fixed choice of the other weights at
+/// most one weight of an invalid entry cancels its error, so the bound is `2^-weight_bits`
+/// regardless of the number of entries, capped at the group order `ℓ ≈ 2^252` for
+/// full-width weights. `BatchVerifier` uses 252 bits by default and 128 bits
+/// `with_short_weights`. An empty batch cannot pass invalidly, so its soundness is infinite.
+/// This is a purely numeric helper.
+pub fn batch_soundness_bits(num_entries: usize, weight_bits: u32) -> f64 {
+    // log2(ℓ) for ℓ = 2^252 + 27742317777372353535851937790883648493.
+    const GROUP_ORDER_BITS: f64 = 252.0;
+    if num_entries == 0 {
+        return f64::INFINITY;
+    }
+    f64::min(weight_bits as f64, GROUP_ORDER_BITS)
+}
+
+/// Thresholds by which `verify_auto_with` picks a verification strategy for a
+/// number of signatures.
+///
+/// The default `batch_threshold` comes from the `verify single` and `verify batch`
+/// benchmarks: a batch of two costs about as much as two single verifications, and
+/// from three signatures on batching is faster. The default `parallel_threshold` has
+/// not been measured; it is a conservative estimate of the batch size at which every
+/// thread gets a sub-batch large enough to amortize scheduling, and should be tuned
+/// for the target machine.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AutoVerifyConfig {
+    /// Fewer signatures than this are verified one by one.
+    pub batch_threshold: usize,
+    /// From this many signatures on, sub-batches are verified in parallel, one per
+    /// rayon thread. Without the `rayon` feature they are verified as a single batch.
+    pub parallel_threshold: usize,
+}
+
+impl Default for AutoVerifyConfig {
+    fn default() -> Self {
+        Self {
+            batch_threshold: 3,
+            parallel_threshold: 256,
+        }
+    }
+}
+
+/// Verifies signatures like `verify_auto_with`, using the default thresholds.
+pub fn verify_auto(
+    items: &[(Transcript, VerificationKey, Signature)],
+) -> Result<(), ZkSchnorrError> {
+    verify_auto_with(items, &AutoVerifyConfig::default())
+}
+
+/// Verifies signatures, picking a strategy by their number: one by one below
+/// `config.batch_threshold`, as one batch below `config.parallel_threshold`, and as
+/// parallel sub-batches above (with the `rayon` feature).
+///
+/// Transcripts should be in the same state as they were during the `sign` calls;
+/// they are cloned, not modified. Fails with `DegenerateSignature` if any signature
+/// is degenerate, and otherwise with `InvalidSignature` when verifying one by one and
+/// `InvalidBatch` when batching.
+pub fn verify_auto_with(
+    items: &[(Transcript, VerificationKey, Signature)],
+    config: &AutoVerifyConfig,
+) -> Result<(), ZkSchnorrError> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    if items.iter().any(|(_, _, sig)| sig.is_degenerate()) {
+        return Err(ZkSchnorrError::DegenerateSignature);
+    }
+    if items.len() < config.batch_threshold {
+        return items.iter().try_for_each(|(transcript, pubkey, sig)| {
+            sig.verify(&mut transcript.clone(), *pubkey)
+        });
+    }
+    #[cfg(feature = "rayon")]
+    if items.len() >= config.parallel_threshold {
+        use rayon::prelude::*;
+
+        let threads = rayon::current_num_threads().max(1);
+        let chunk = items.len().div_ceil(threads);
+        return items.par_chunks(chunk).try_for_each(verify_serial_batch);
+    }
+    verify_serial_batch(items)
+}
+
+fn verify_serial_batch(
+    items: &[(Transcript, VerificationKey, Signature)],
+) -> Result<(), ZkSchnorrError> {
+    let mut batch = BatchVerifier::with_capacity(default_rng(), items.len());
+    for (transcript, pubkey, sig) in items {
+        sig.verify_batched(&mut transcript.clone(), *pubkey, &mut batch);
     }
+    batch.verify()
 }
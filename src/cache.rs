@@ -0,0 +1,110 @@
+//! Verification cache, enabled with the `cache` feature.
+//!
+//! `VerificationCache` remembers the result of each verification by signature, key and
+//! transcript state, and evicts the least recently used result when it is full.
+
+use merlin::Transcript;
+use std::collections::{BTreeMap, HashMap};
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::{compute_challenge, Signature};
+use super::transcript::CACHE_DIGEST_LABEL;
+
+/// Signature bytes, key bytes and a digest of the transcript state before verification.
+type CacheKey = [u8; 160];
+
+/// Memoizes verification results, so that re-verifying the same signature under the
+/// same key and transcript (e.g. on an idempotent retry) skips the multiscalar
+/// multiplication.
+///
+/// Entries are keyed by the signature and key encodings together with a digest
+/// squeezed from a copy of the transcript, so a different message or transcript
+/// history never hits an entry. At most `capacity` results are kept; when full,
+/// the least recently used entry is evicted.
+#[derive(Clone, Debug)]
+pub struct VerificationCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, (u64, Result<(), ZkSchnorrError>)>,
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    hits: u64,
+}
+
+impl VerificationCache {
+    /// Creates a cache holding up to `capacity` results; a zero capacity caches nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+        }
+    }
+
+    /// Verifies `signature` like `Signature::verify`, returning the cached result if
+    /// the same signature was verified before under the same key and transcript state.
+    /// The transcript is left in the same state as with `verify` in either case.
+    pub fn verify(
+        &mut self,
+        signature: &Signature,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        let key = cache_key(signature, transcript, &pubkey);
+        self.tick += 1;
+        if let Some((used, result)) = self.entries.get_mut(&key) {
+            self.recency.remove(used);
+            self.recency.insert(self.tick, key);
+            *used = self.tick;
+            self.hits += 1;
+            compute_challenge(transcript, &pubkey, &signature.R);
+            return result.clone();
+        }
+
+        let result = signature.verify(transcript, pubkey);
+        if self.capacity == 0 {
+            return result;
+        }
+        if self.entries.len() == self.capacity {
+            // `BTreeMap::pop_first` would need Rust 1.66.
+            let oldest = self.recency.keys().next().copied();
+            if let Some(evicted) = oldest.and_then(|tick| self.recency.remove(&tick)) {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.entries.insert(key, (self.tick, result.clone()));
+        self.recency.insert(self.tick, key);
+        result
+    }
+
+    /// Returns the number of verifications answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+fn cache_key(signature: &Signature, transcript: &Transcript, pubkey: &VerificationKey) -> CacheKey {
+    let mut key = [0u8; 160];
+    key[..64].copy_from_slice(&signature.to_bytes());
+    key[64..128].copy_from_slice(&pubkey.to_bytes_array());
+    transcript.clone().challenge_bytes(CACHE_DIGEST_LABEL, &mut key[128..]);
+    key
+}
@@ -0,0 +1,56 @@
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::{
+    TranscriptProtocol, COMMITMENTS_TRANSCRIPT_LABEL, COMMITMENT_COUNT_LABEL,
+    COMMITMENT_INDEX_LABEL,
+};
+
+fn transcript_for_commitments(
+    label: &'static [u8],
+    commitments: &[CompressedRistretto],
+) -> Transcript {
+    let mut t = Transcript::new(COMMITMENTS_TRANSCRIPT_LABEL);
+    t.append_u64(COMMITMENT_COUNT_LABEL, commitments.len() as u64);
+    for (i, commitment) in commitments.iter().enumerate() {
+        t.append_u64(COMMITMENT_INDEX_LABEL, i as u64);
+        t.append_point(label, commitment);
+    }
+    t
+}
+
+impl Signature {
+    /// Signs a vector of commitments, e.g. Pedersen commitments to transaction amounts.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_commitments",
+    /// appends the number of commitments labelled "n", and then each commitment,
+    /// preceded by its index labelled "index", labelled with a user-provided `label`.
+    ///
+    /// The whole vector is bound in order: the signature does not verify for a
+    /// reordered, truncated or extended vector.
+    pub fn sign_commitments(
+        label: &'static [u8],
+        commitments: &[CompressedRistretto],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut transcript_for_commitments(label, commitments),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_commitments` over the same vector.
+    pub fn verify_commitments(
+        &self,
+        label: &'static [u8],
+        commitments: &[CompressedRistretto],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(&mut transcript_for_commitments(label, commitments), pubkey)
+    }
+}
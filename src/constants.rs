@@ -0,0 +1,15 @@
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+/// The Ristretto base point `B` (see "Base point" in `docs/spec.md`).
+/// Verification keys built with `VerificationKey::from_secret` have the generator
+/// `G = r·B`, against which signatures are checked.
+pub const BASEPOINT: RistrettoPoint = RISTRETTO_BASEPOINT_POINT;
+
+/// Little-endian bytes of the order `ℓ = 2^252 + 27742317777372353535851937790883648493`
+/// of the Ristretto group. Scalars (private keys, nonces, challenges and `s`) are
+/// integers modulo `ℓ`. The order is given as bytes, since as a `Scalar` it reduces to zero.
+pub const GROUP_ORDER_BYTES: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
@@ -0,0 +1,64 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::batch::BatchVerification;
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+
+/// Signing context shared by signer and verifier.
+///
+/// Both sides construct the context identically, from a `domain` and the same
+/// labelled fields in the same order, and then call `sign` and `verify` on it. The
+/// context owns the transcript built from these inputs and both operations start
+/// from a copy of it, so the transcript is built in one place only and cannot
+/// diverge between signing and verifying.
+#[derive(Clone)]
+pub struct Context {
+    transcript: Transcript,
+}
+
+impl Context {
+    /// Creates a context whose transcript is labelled `domain`, e.g. `b"payments.transfer"`.
+    pub fn new(domain: &'static [u8]) -> Self {
+        Context {
+            transcript: Transcript::new(domain),
+        }
+    }
+
+    /// Binds a field with the given label to the context.
+    pub fn append(mut self, label: &'static [u8], bytes: impl AsRef<[u8]>) -> Self {
+        self.transcript.append_message(label, bytes.as_ref());
+        self
+    }
+
+    /// Returns a copy of the transcript signed and verified by the context,
+    /// e.g. to use it with other transcript-based APIs.
+    pub fn transcript(&self) -> Transcript {
+        self.transcript.clone()
+    }
+
+    /// Signs the context.
+    pub fn sign(&self, pubkey: VerificationKey, privkey: Scalar) -> Signature {
+        Signature::sign(&mut self.transcript(), pubkey, privkey)
+    }
+
+    /// Verifies a signature created with `sign` on an identically constructed context.
+    pub fn verify(
+        &self,
+        signature: &Signature,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        signature.verify(&mut self.transcript(), pubkey)
+    }
+
+    /// Adds a signature created with `sign` to a batch, like `Signature::verify_batched`.
+    pub fn verify_batched(
+        &self,
+        signature: &Signature,
+        pubkey: VerificationKey,
+        batch: &mut impl BatchVerification,
+    ) {
+        signature.verify_batched(&mut self.transcript(), pubkey, batch)
+    }
+}
@@ -0,0 +1,42 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::COUNTERSIGN_TRANSCRIPT_LABEL;
+
+/// Countersigns `original`, a signature by `original_key`, with the key of a timestamp
+/// authority (or another notary). The countersignature covers the encoded original
+/// signature and its signer's key, under a domain distinct from ordinary signatures.
+/// The notary does not see or check the originally signed message.
+pub fn countersign(
+    original: &Signature,
+    original_key: VerificationKey,
+    ts_key: VerificationKey,
+    ts_privkey: Scalar,
+) -> Signature {
+    Signature::sign(
+        &mut countersign_transcript(original, &original_key),
+        ts_key,
+        ts_privkey,
+    )
+}
+
+/// Verifies that `counter` is a countersignature by `ts_key` of `original` as signed by
+/// `original_key`. The original signature must be verified over its message separately.
+pub fn verify_countersigned(
+    original: &Signature,
+    counter: &Signature,
+    original_key: VerificationKey,
+    ts_key: VerificationKey,
+) -> Result<(), ZkSchnorrError> {
+    counter.verify(&mut countersign_transcript(original, &original_key), ts_key)
+}
+
+fn countersign_transcript(original: &Signature, original_key: &VerificationKey) -> Transcript {
+    let mut t = Transcript::new(COUNTERSIGN_TRANSCRIPT_LABEL);
+    t.append_message(b"original_key", &original_key.to_bytes_array());
+    t.append_message(b"original", &original.to_bytes());
+    t
+}
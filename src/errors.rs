@@ -1,4 +1,5 @@
 use thiserror::Error;
+
 /// Represents an error in key aggregation, signing, or verification.
 #[derive(Error, Clone, Debug, Eq, PartialEq)]
 pub enum ZkSchnorrError {
@@ -9,4 +10,98 @@ pub enum ZkSchnorrError {
     /// This error occurs when a set of signatures failed to verify as a batch
     #[error("Batch signature verification failed")]
     InvalidBatch,
+
+    /// This error occurs when an output buffer is too small for the encoding
+    #[error("Output buffer is too small")]
+    BufferTooSmall,
+
+    /// This error occurs when a point is not a valid element of the prime-order group
+    #[error("Point is not a valid prime-order group element")]
+    InvalidPoint,
+
+    /// This error occurs when a batch holds more entries than the verifier allows
+    #[error("Batch has too many entries")]
+    BatchTooLarge,
+
+    /// This error occurs when a scalar is not canonically encoded
+    #[error("Scalar is not canonically encoded")]
+    InvalidScalar,
+
+    /// This error occurs when a key structure proof is not valid
+    #[error("Key structure proof verification failed")]
+    InvalidProof,
+
+    /// This error occurs when a signature with `R` at the identity or a zero `s` is verified
+    #[error("Degenerate signature")]
+    DegenerateSignature,
+
+    /// This error occurs when a sealed signing key is malformed or cannot be decrypted with the passphrase
+    #[error("Invalid sealed key or wrong passphrase")]
+    InvalidSealedKey,
+
+    /// This error occurs when a key rotation chain has an invalid link or ends at another key
+    #[error("Key rotation chain is invalid")]
+    InvalidKeyChain,
+
+    /// This error occurs when a batch contains signatures by revoked keys, at the given entry indices
+    #[error("Batch contains revoked keys at entries {0:?}")]
+    RevokedKeys(Vec<usize>),
+
+    /// This error occurs when a group key is combined from an empty list of key shares
+    #[error("No key shares to combine")]
+    NoKeyShares,
+
+    /// This error occurs when reading a streamed message fails
+    #[error("Failed to read message: {0:?}")]
+    MessageRead(std::io::ErrorKind),
+
+    /// This error occurs when strict verification finds a key or signature point at the identity
+    #[error("Point is the identity")]
+    IdentityPoint,
+
+    /// This error occurs when armored text has a wrong header or footer or a malformed body
+    #[error("Invalid armored text")]
+    InvalidArmor,
+
+    /// This error occurs when a key is blinded with a zero shared secret
+    #[error("Shared secret is zero")]
+    ZeroSharedSecret,
+
+    /// This error occurs when a multi-signer check is given no signers
+    #[error("No signers to verify")]
+    NoSigners,
+}
+
+/// Reason why a signature failed to verify, returned by `Signature::verify_detailed`.
+#[derive(Error, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VerifyFailure {
+    /// A point of the verification key is not a valid encoding
+    #[error("Verification key point is malformed")]
+    MalformedKey,
+
+    /// The nonce commitment `R` is not a valid encoding
+    #[error("Signature nonce commitment is malformed")]
+    MalformedR,
+
+    /// The signature scalar `s` is not reduced modulo the group order
+    #[error("Signature scalar is not canonical")]
+    NonCanonicalScalar,
+
+    /// The encodings are valid but `s·G = R + c·H` does not hold,
+    /// e.g. because of a wrong key or message
+    #[error("Signature equation does not hold")]
+    EquationMismatch,
+
+    /// `R` is the identity or `s` is zero (see `Signature::is_degenerate`)
+    #[error("Degenerate signature")]
+    Degenerate,
+}
+
+impl From<VerifyFailure> for ZkSchnorrError {
+    fn from(failure: VerifyFailure) -> Self {
+        match failure {
+            VerifyFailure::Degenerate => ZkSchnorrError::DegenerateSignature,
+            _ => ZkSchnorrError::InvalidSignature,
+        }
+    }
 }
@@ -0,0 +1,213 @@
+//! C ABI over the message-oriented API.
+//!
+//! Keys and signatures cross the boundary as fixed-size byte arrays: 32-byte signing
+//! keys, 64-byte verification keys (`G || H`) and 64-byte signatures (`R || s`).
+//! Messages are signed with the label `FFI_MESSAGE_LABEL` through `Signature::sign_message`,
+//! so they can also be verified with `Signature::verify_message` on the Rust side.
+//! Every function returns `ZKSCHNORR_OK` or a negative error code and never panics
+//! across the boundary.
+
+use core::slice;
+use curve25519_dalek::scalar::Scalar;
+use rand_core::RngCore;
+
+use super::batch::Verifier;
+use super::key::{PreparedVerificationKey, SigningKey, SigningKeyBytes, VerificationKey};
+use super::signature::{default_rng, Signature};
+
+/// Label under which messages are appended to the transcript by the FFI functions.
+pub const FFI_MESSAGE_LABEL: &[u8] = b"message";
+
+/// The operation succeeded.
+pub const ZKSCHNORR_OK: i32 = 0;
+/// A required pointer was null.
+pub const ZKSCHNORR_ERR_NULL_POINTER: i32 = -1;
+/// The signing key is not a canonical scalar.
+pub const ZKSCHNORR_ERR_INVALID_SIGNING_KEY: i32 = -2;
+/// The verification key does not consist of two valid points.
+pub const ZKSCHNORR_ERR_INVALID_VERIFICATION_KEY: i32 = -3;
+/// The signature is malformed or does not verify.
+pub const ZKSCHNORR_ERR_INVALID_SIGNATURE: i32 = -4;
+/// At least one signature in the batch does not verify.
+pub const ZKSCHNORR_ERR_INVALID_BATCH: i32 = -5;
+
+/// Generates a random signing key and its verification key.
+///
+/// # Safety
+///
+/// `signing_key_out` must be valid for writes of 32 bytes and
+/// `verification_key_out` for writes of 64 bytes.
+#[no_mangle]
+pub unsafe extern "C" fn zkschnorr_generate_keypair(
+    signing_key_out: *mut u8,
+    verification_key_out: *mut u8,
+) -> i32 {
+    if signing_key_out.is_null() || verification_key_out.is_null() {
+        return ZKSCHNORR_ERR_NULL_POINTER;
+    }
+    let mut rng = default_rng();
+    let mut wide = [0u8; 64];
+    rng.fill_bytes(&mut wide);
+    let privkey = Scalar::from_bytes_mod_order_wide(&wide);
+    rng.fill_bytes(&mut wide);
+    let r = Scalar::from_bytes_mod_order_wide(&wide);
+
+    let pubkey = VerificationKey::from_secret(&privkey, &r);
+    slice::from_raw_parts_mut(signing_key_out, 32).copy_from_slice(&privkey.to_scalar_bytes());
+    slice::from_raw_parts_mut(verification_key_out, 64).copy_from_slice(&pubkey.to_bytes_array());
+    ZKSCHNORR_OK
+}
+
+/// Signs `message_len` bytes at `message` and writes the 64-byte signature.
+///
+/// # Safety
+///
+/// `signing_key` must be valid for reads of 32 bytes, `verification_key` for reads
+/// of 64 bytes, `signature_out` for writes of 64 bytes and `message` for reads of
+/// `message_len` bytes. `message` may be null only if `message_len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn zkschnorr_sign(
+    signing_key: *const u8,
+    verification_key: *const u8,
+    message: *const u8,
+    message_len: usize,
+    signature_out: *mut u8,
+) -> i32 {
+    if signing_key.is_null() || verification_key.is_null() || signature_out.is_null() {
+        return ZKSCHNORR_ERR_NULL_POINTER;
+    }
+    let message = match message_slice(message, message_len) {
+        Some(message) => message,
+        None => return ZKSCHNORR_ERR_NULL_POINTER,
+    };
+    let mut key_bytes = [0u8; 32];
+    key_bytes.copy_from_slice(slice::from_raw_parts(signing_key, 32));
+    let privkey = match SigningKey::from_scalar_bytes(key_bytes) {
+        Ok(privkey) => privkey,
+        Err(_) => return ZKSCHNORR_ERR_INVALID_SIGNING_KEY,
+    };
+    let pubkey = match read_verification_key(verification_key) {
+        Some(pubkey) => pubkey,
+        None => return ZKSCHNORR_ERR_INVALID_VERIFICATION_KEY,
+    };
+
+    let signature = Signature::sign_message(FFI_MESSAGE_LABEL, message, pubkey, privkey);
+    slice::from_raw_parts_mut(signature_out, 64).copy_from_slice(&signature.to_bytes());
+    ZKSCHNORR_OK
+}
+
+/// Verifies a 64-byte signature over `message_len` bytes at `message`.
+///
+/// # Safety
+///
+/// `verification_key` and `signature` must be valid for reads of 64 bytes and
+/// `message` for reads of `message_len` bytes. `message` may be null only if
+/// `message_len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn zkschnorr_verify(
+    verification_key: *const u8,
+    message: *const u8,
+    message_len: usize,
+    signature: *const u8,
+) -> i32 {
+    if verification_key.is_null() || signature.is_null() {
+        return ZKSCHNORR_ERR_NULL_POINTER;
+    }
+    let message = match message_slice(message, message_len) {
+        Some(message) => message,
+        None => return ZKSCHNORR_ERR_NULL_POINTER,
+    };
+    let pubkey = match read_verification_key(verification_key) {
+        Some(pubkey) => pubkey,
+        None => return ZKSCHNORR_ERR_INVALID_VERIFICATION_KEY,
+    };
+    match Signature::from_bytes(slice::from_raw_parts(signature, 64))
+        .and_then(|signature| signature.verify_message(FFI_MESSAGE_LABEL, message, pubkey))
+    {
+        Ok(()) => ZKSCHNORR_OK,
+        Err(_) => ZKSCHNORR_ERR_INVALID_SIGNATURE,
+    }
+}
+
+/// Verifies `count` signatures at once. Entry `i` consists of the 64-byte verification
+/// key at `verification_keys + 64·i`, the message of `message_lens[i]` bytes at
+/// `messages[i]` and the 64-byte signature at `signatures + 64·i`.
+///
+/// Returns `ZKSCHNORR_ERR_INVALID_BATCH` if two or more signatures are given and any of
+/// them fails, without telling which one; a single signature fails as in `zkschnorr_verify`.
+///
+/// # Safety
+///
+/// `verification_keys` and `signatures` must be valid for reads of `64·count` bytes,
+/// `messages` and `message_lens` for reads of `count` elements, and each `messages[i]`
+/// for reads of `message_lens[i]` bytes. All pointers may be null if `count` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn zkschnorr_batch_verify(
+    verification_keys: *const u8,
+    messages: *const *const u8,
+    message_lens: *const usize,
+    signatures: *const u8,
+    count: usize,
+) -> i32 {
+    if count == 0 {
+        return ZKSCHNORR_OK;
+    }
+    if verification_keys.is_null()
+        || messages.is_null()
+        || message_lens.is_null()
+        || signatures.is_null()
+    {
+        return ZKSCHNORR_ERR_NULL_POINTER;
+    }
+    let total = match count.checked_mul(64) {
+        Some(total) => total,
+        None => return ZKSCHNORR_ERR_INVALID_BATCH,
+    };
+    let keys = slice::from_raw_parts(verification_keys, total);
+    let sigs = slice::from_raw_parts(signatures, total);
+    let messages = slice::from_raw_parts(messages, count);
+    let message_lens = slice::from_raw_parts(message_lens, count);
+
+    let mut verifier = Verifier::new();
+    for i in 0..count {
+        let message = match message_slice(messages[i], message_lens[i]) {
+            Some(message) => message,
+            None => return ZKSCHNORR_ERR_NULL_POINTER,
+        };
+        let pubkey = match VerificationKey::from_bytes(&keys[64 * i..64 * (i + 1)]) {
+            Ok(pubkey) => pubkey,
+            Err(_) => return ZKSCHNORR_ERR_INVALID_VERIFICATION_KEY,
+        };
+        let signature = match Signature::from_bytes(&sigs[64 * i..64 * (i + 1)]) {
+            Ok(signature) => signature,
+            Err(_) => return ZKSCHNORR_ERR_INVALID_SIGNATURE,
+        };
+        let transcript = Signature::transcript_for_message(FFI_MESSAGE_LABEL, message);
+        if verifier.push(signature, transcript, pubkey).is_err() {
+            return ZKSCHNORR_ERR_INVALID_SIGNATURE;
+        }
+    }
+    match verifier.verify() {
+        Ok(()) => ZKSCHNORR_OK,
+        Err(_) if count == 1 => ZKSCHNORR_ERR_INVALID_SIGNATURE,
+        Err(_) => ZKSCHNORR_ERR_INVALID_BATCH,
+    }
+}
+
+unsafe fn message_slice<'a>(message: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if message.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(message, len))
+    }
+}
+
+/// Reads a verification key, checking that both points decompress
+/// so that signing cannot fail later on.
+unsafe fn read_verification_key(bytes: *const u8) -> Option<VerificationKey> {
+    let pubkey = VerificationKey::from_bytes(slice::from_raw_parts(bytes, 64)).ok()?;
+    PreparedVerificationKey::prepare(pubkey).ok()?;
+    Some(pubkey)
+}
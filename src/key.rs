@@ -1,20 +1,61 @@
-use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::constants::{RISTRETTO_BASEPOINT_COMPRESSED, RISTRETTO_BASEPOINT_POINT};
 use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar;
-//use serde::{Deserialize, Serialize};
+use curve25519_dalek::traits::Identity;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::metrics::decompress;
+use super::transcript::{TranscriptProtocol, FINGERPRINT_TRANSCRIPT_LABEL, G_LABEL, H_LABEL};
 
 /// Signing key (aka "privkey") is a type alias for the scalar in Ristretto255 group.
 pub type SigningKey = Scalar;
 
+/// Conversions of a `SigningKey` to and from its byte encoding, so that callers can
+/// hold keys as `[u8; 32]` without depending on the `Scalar` API of `curve25519-dalek`.
+///
+/// The encoding is the canonical 32-byte little-endian form of the scalar, as described
+/// in the specification. It is part of this crate's stable surface and will not change
+/// across upgrades of the underlying curve library.
+pub trait SigningKeyBytes: Sized {
+    /// Decodes a signing key, failing if the bytes are not a canonical scalar.
+    fn from_scalar_bytes(bytes: [u8; 32]) -> Result<Self, ZkSchnorrError>;
+    /// Encodes a signing key as 32 little-endian bytes.
+    fn to_scalar_bytes(&self) -> [u8; 32];
+}
+
+impl SigningKeyBytes for SigningKey {
+    fn from_scalar_bytes(bytes: [u8; 32]) -> Result<Self, ZkSchnorrError> {
+        Scalar::from_canonical_bytes(bytes).ok_or(ZkSchnorrError::InvalidScalar)
+    }
+
+    fn to_scalar_bytes(&self) -> [u8; 32] {
+        self.to_bytes()
+    }
+}
+
 /// Verification key (aka "pubkey") is a wrapper type around two Ristretto points
 /// that lets the verifier to check the signature.
-#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]//, Serialize, Deserialize)]
-//#[serde(from = "CompressedRistretto", into = "CompressedRistretto")]
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
 pub struct VerificationKey {
   pub(crate)  g: CompressedRistretto,     //G.r
   pub(crate)  h: CompressedRistretto,     //(G.r).sk
 }
 
+/// Orders keys by their canonical 64-byte encoding (`G` then `H`), e.g. to sort a key
+/// set into the same order for every participant.
+impl Ord for VerificationKey {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.to_bytes_array().cmp(&other.to_bytes_array())
+    }
+}
+
+impl PartialOrd for VerificationKey {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl VerificationKey {
 
   ///set verification key
@@ -24,10 +65,21 @@ impl VerificationKey {
     /// Constructs a VerificationKey from a private key and some randomness.
     pub fn from_secret(privkey: &Scalar, r: &Scalar) -> Self {
         let g = Self::from_secret_decompressed(r);
-        let h = privkey * &g;
+        let h = privkey * g;
         Self::from_compressed(g.compress(),h.compress())
     }
 
+    /// Reconstructs a VerificationKey from a private key and a stored base `g`,
+    /// computing `h = privkey·g`. Fails with `InvalidPoint` if `g` does not decompress.
+    pub fn from_signing_key_and_base(
+        privkey: &Scalar,
+        g: &CompressedRistretto,
+    ) -> Result<Self, ZkSchnorrError> {
+        let base = decompress(g).ok_or(ZkSchnorrError::InvalidPoint)?;
+        let h = privkey * base;
+        Ok(Self::from_compressed(*g, h.compress()))
+    }
+
     /// Constructs first point of VerificationKey from randomness.
     pub fn from_secret_decompressed(r: &Scalar) -> RistrettoPoint {
         r * RISTRETTO_BASEPOINT_POINT
@@ -41,6 +93,81 @@ impl VerificationKey {
         }
     }
 
+    /// Returns the effective public point `H = x·G`, for consumers that expect a
+    /// single-point public key with `G` as a per-key generator (see `from_effective`).
+    ///
+    /// The two-point form `(G, H)` is what signatures verify against and what should be
+    /// stored and transmitted; `H` alone identifies a key only together with its `G`.
+    pub fn effective_point(&self) -> CompressedRistretto {
+        self.h
+    }
+
+    /// Creates a key from a per-key generator `g` and the effective public point `h = x·g`,
+    /// the inverse of `effective_point`. The result is the same as `from_compressed(g, h)`.
+    pub fn from_effective(g: CompressedRistretto, h: CompressedRistretto) -> Self {
+        Self::from_compressed(g, h)
+    }
+
+    /// Derives a one-time verification key from this key and a `shared_secret` `t`:
+    /// `(t·G, t·(H + t·G))`. The matching signing key is `blind_signing_key(x, t)`,
+    /// i.e. `x + t`, so only the holder of the base signing key can sign for it.
+    ///
+    /// Unlike plain rerandomization `(t·G, t·H)`, which keeps the signing key unchanged,
+    /// blinding also shifts the secret, so signatures made under the one-time key are
+    /// not valid under any rerandomization of the base key. Without `t` the derived
+    /// key cannot be linked to the base key. Fails with `ZeroSharedSecret` if
+    /// `shared_secret` is zero, which would give the identity key.
+    pub fn blind(&self, shared_secret: &Scalar) -> Result<Self, ZkSchnorrError> {
+        if shared_secret == &Scalar::zero() {
+            return Err(ZkSchnorrError::ZeroSharedSecret);
+        }
+        let g = decompress(&self.g).ok_or(ZkSchnorrError::InvalidPoint)?;
+        let h = decompress(&self.h).ok_or(ZkSchnorrError::InvalidPoint)?;
+        let blinded_g = g * shared_secret;
+        let blinded_h = (h + blinded_g) * shared_secret;
+        Ok(Self::from_compressed(blinded_g.compress(), blinded_h.compress()))
+    }
+
+    /// Returns the signing key matching `VerificationKey::blind` with the same `shared_secret`.
+    /// Fails with `ZeroSharedSecret` if `shared_secret` is zero, like `blind`.
+    pub fn blind_signing_key(
+        privkey: &SigningKey,
+        shared_secret: &Scalar,
+    ) -> Result<SigningKey, ZkSchnorrError> {
+        if shared_secret == &Scalar::zero() {
+            return Err(ZkSchnorrError::ZeroSharedSecret);
+        }
+        Ok(privkey + shared_secret)
+    }
+
+    /// Returns `true` if the key's generator `g` is the standard Ristretto base point,
+    /// as for keys made with `from_secret` and `r = 1`. This compares encodings only,
+    /// without decompressing, and lets signing use precomputed base point tables.
+    pub fn uses_standard_base(&self) -> bool {
+        self.g == RISTRETTO_BASEPOINT_COMPRESSED
+    }
+
+    /// Combines key shares into a group key by summing their `g` points and their `h`
+    /// points respectively, as in a DKG where the group key is the sum of the shares.
+    /// Fails with `NoKeyShares` for empty input and `InvalidPoint` if a share does
+    /// not decompress.
+    ///
+    /// Signatures verify under the group key only if it has a signing key, e.g. when
+    /// all `n` shares use the same `g`: with share keys `x_i` the group key is then
+    /// `(x_1 + ... + x_n) / n`, since `G = n·g` and `H = (x_1 + ... + x_n)·g`.
+    pub fn from_shares(shares: &[VerificationKey]) -> Result<Self, ZkSchnorrError> {
+        if shares.is_empty() {
+            return Err(ZkSchnorrError::NoKeyShares);
+        }
+        let mut g = RistrettoPoint::identity();
+        let mut h = RistrettoPoint::identity();
+        for share in shares {
+            g += decompress(&share.g).ok_or(ZkSchnorrError::InvalidPoint)?;
+            h += decompress(&share.h).ok_or(ZkSchnorrError::InvalidPoint)?;
+        }
+        Ok(Self::from_compressed(g.compress(), h.compress()))
+    }
+
     /// Converts the Verification key to compressed points
     pub fn into_point(self) -> (CompressedRistretto, CompressedRistretto) {
         (self.g, self.h)
@@ -51,6 +178,35 @@ impl VerificationKey {
         (&self.g, &self.h)
     }
 
+    /// Returns `true` if both `G` and `H` decompress to valid Ristretto points,
+    /// which are always elements of the prime-order group.
+    /// This does not reject the identity point.
+    pub fn points_valid(&self) -> bool {
+        decompress(&self.g).is_some() && decompress(&self.h).is_some()
+    }
+
+    /// Returns a short identifier of the key for directory lookups: the first 16 bytes
+    /// of a Merlin transcript hash of `G` and `H` under the label "ZkSchnorr.fingerprint".
+    ///
+    /// A fingerprint is truncated to 128 bits, so collisions can be found with about
+    /// 2^64 work; it names a key but proves nothing, and is not a signature.
+    pub fn fingerprint(&self) -> [u8; 16] {
+        let mut transcript = Transcript::new(FINGERPRINT_TRANSCRIPT_LABEL);
+        transcript.append_point(G_LABEL, &self.g);
+        transcript.append_point(H_LABEL, &self.h);
+        let mut fingerprint = [0u8; 16];
+        transcript.challenge_bytes(b"fingerprint", &mut fingerprint);
+        fingerprint
+    }
+
+    /// Returns the byte representation of the verification key as a fixed-size array
+    pub fn to_bytes_array(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(self.g.as_bytes());
+        bytes[32..].copy_from_slice(self.h.as_bytes());
+        bytes
+    }
+
     /// Returns the byte representation of the verification key
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::with_capacity(64);
@@ -58,5 +214,70 @@ impl VerificationKey {
         bytes.extend_from_slice(self.h.as_bytes());
         bytes
     }
+
+    /// Decodes the verification key from a 64-byte slice `G || H`.
+    /// Like `from_compressed`, the points are not decompressed here.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZkSchnorrError> {
+        if bytes.len() != 64 {
+            return Err(ZkSchnorrError::InvalidPoint);
+        }
+        let mut g = [0u8; 32];
+        let mut h = [0u8; 32];
+        g.copy_from_slice(&bytes[..32]);
+        h.copy_from_slice(&bytes[32..]);
+        Ok(Self::from_compressed(CompressedRistretto(g), CompressedRistretto(h)))
+    }
+
+    /// Encodes the verification key into the first 64 bytes of `out`, without allocating.
+    /// Fails if `out` is shorter than 64 bytes.
+    pub fn encode_into(&self, out: &mut [u8]) -> Result<(), ZkSchnorrError> {
+        if out.len() < 64 {
+            return Err(ZkSchnorrError::BufferTooSmall);
+        }
+        out[..32].copy_from_slice(self.g.as_bytes());
+        out[32..64].copy_from_slice(self.h.as_bytes());
+        Ok(())
+    }
 }
 
+/// Verification key together with its decompressed points, so that signing and
+/// verification don't need to decompress them again.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct PreparedVerificationKey {
+    key: VerificationKey,
+    g: RistrettoPoint,
+    h: RistrettoPoint,
+}
+
+impl PreparedVerificationKey {
+    /// Decompresses the points of `key`, failing if either is not a valid encoding.
+    pub fn prepare(key: VerificationKey) -> Result<Self, ZkSchnorrError> {
+        let g = decompress(&key.g).ok_or(ZkSchnorrError::InvalidPoint)?;
+        let h = decompress(&key.h).ok_or(ZkSchnorrError::InvalidPoint)?;
+        Ok(Self { key, g, h })
+    }
+
+    /// Returns the compressed verification key.
+    pub fn key(&self) -> VerificationKey {
+        self.key
+    }
+
+    /// Returns references to the decompressed points `(G, H)`.
+    pub fn points(&self) -> (&RistrettoPoint, &RistrettoPoint) {
+        (&self.g, &self.h)
+    }
+}
+
+impl VerificationKey {
+    /// Constructs a prepared VerificationKey from a private key and some randomness,
+    /// keeping the computed points instead of recompressing and decompressing them.
+    pub fn from_secret_prepared(privkey: &Scalar, r: &Scalar) -> PreparedVerificationKey {
+        let g = Self::from_secret_decompressed(r);
+        let h = privkey * g;
+        PreparedVerificationKey {
+            key: Self::from_compressed(g.compress(), h.compress()),
+            g,
+            h,
+        }
+    }
+}
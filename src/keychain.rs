@@ -0,0 +1,61 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::KEY_ROTATION_TRANSCRIPT_LABEL;
+
+/// Certificate that a key was superseded by `new_key`: a signature by the old key
+/// over the new one. A sequence of certificates forms a chain from a root key
+/// to the current key, checked with `verify_chain`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RotationCert {
+    /// The key replacing the signer's key
+    pub new_key: VerificationKey,
+    /// Signature by the old key over `new_key`
+    pub signature: Signature,
+}
+
+impl RotationCert {
+    /// Rotates the key `from_secret(old_privkey, old_r)` to `new_key`.
+    pub fn rotate(old_privkey: &Scalar, old_r: &Scalar, new_key: VerificationKey) -> Self {
+        let old_key = VerificationKey::from_secret(old_privkey, old_r);
+        let signature = Signature::sign(&mut rotation_transcript(&new_key), old_key, *old_privkey);
+        Self { new_key, signature }
+    }
+
+    /// Verifies that the certificate was signed by `old_key`.
+    pub fn verify(&self, old_key: VerificationKey) -> Result<(), ZkSchnorrError> {
+        self.signature
+            .verify(&mut rotation_transcript(&self.new_key), old_key)
+    }
+}
+
+/// Verifies that `certs` rotate `root_key` to `leaf_key`, link by link.
+/// An empty chain is valid only if the leaf is the root.
+/// Fails with `InvalidKeyChain` on the first invalid certificate, or if the
+/// chain ends at a different key.
+pub fn verify_chain(
+    root_key: VerificationKey,
+    certs: &[RotationCert],
+    leaf_key: VerificationKey,
+) -> Result<(), ZkSchnorrError> {
+    let mut current = root_key;
+    for cert in certs {
+        cert.verify(current)
+            .map_err(|_| ZkSchnorrError::InvalidKeyChain)?;
+        current = cert.new_key;
+    }
+    if current == leaf_key {
+        Ok(())
+    } else {
+        Err(ZkSchnorrError::InvalidKeyChain)
+    }
+}
+
+fn rotation_transcript(new_key: &VerificationKey) -> Transcript {
+    let mut t = Transcript::new(KEY_ROTATION_TRANSCRIPT_LABEL);
+    t.append_message(b"new_key", &new_key.to_bytes_array());
+    t
+}
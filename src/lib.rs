@@ -2,19 +2,97 @@
 #![allow(non_snake_case)]
 //! Schnorr signature implementation.
 
+mod abstain;
+mod aggregate;
 mod batch;
+#[cfg(feature = "cache")]
+mod cache;
+mod commitments;
+mod constants;
+mod context;
+mod counter;
+mod countersign;
 mod errors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod key;
+mod keychain;
+mod message;
+mod metrics;
+mod prehash;
+mod protocol;
+mod reader;
+#[cfg(feature = "debug-transcript")]
+mod recorder;
+mod request;
+#[cfg(feature = "encrypted-keys")]
+mod sealed;
 mod serialization;
+mod session;
+mod short;
+mod signable;
 mod signature;
+mod structure;
 mod transcript;
+mod verified;
 
 #[cfg(test)]
 mod tests;
 
-pub use self::batch::{BatchVerification, BatchVerifier, SingleVerifier};
-pub use self::errors::ZkSchnorrError;
-pub use self::key::{SigningKey, VerificationKey};
-pub use self::signature::Signature;
-pub use self::transcript::TranscriptProtocol;
+pub use self::aggregate::aggregate_keys;
+pub use self::batch::{
+    batch_soundness_bits, verify_auto, verify_auto_with, verify_batch_lazy, verify_stream,
+    AutoVerifyConfig, BatchVerification, BatchVerifier, DalekBackend, MultiscalarBackend,
+    SingleVerifier, Verifier,
+};
+#[cfg(feature = "cache")]
+pub use self::cache::VerificationCache;
+pub use self::constants::{BASEPOINT, GROUP_ORDER_BYTES};
+pub use self::context::Context;
+pub use self::countersign::{countersign, verify_countersigned};
+pub use self::errors::{VerifyFailure, ZkSchnorrError};
+pub use self::key::{PreparedVerificationKey, SigningKey, SigningKeyBytes, VerificationKey};
+pub use self::keychain::{verify_chain, RotationCert};
+pub use self::message::MessageBuilder;
+#[cfg(feature = "metrics")]
+pub use self::metrics::{decompression_count, reset_decompression_count};
+#[cfg(feature = "prehash")]
+pub use self::prehash::{Hasher, PrehashDigest};
+pub use self::protocol::ProtocolId;
+#[cfg(feature = "debug-transcript")]
+pub use self::recorder::{
+    RecordedVerifyError, RecordingTranscript, TranscriptDivergence, TranscriptLog,
+};
+#[cfg(feature = "encrypted-keys")]
+pub use self::sealed::SigningKeySeal;
+#[cfg(feature = "base32")]
+pub use self::serialization::BASE32_LEN;
+pub use self::serialization::{scalar_from_bytes_be, scalar_to_bytes_be};
+pub use self::session::{SessionCommitment, SigningSession};
+pub use self::short::ShortSignature;
+pub use self::signable::{Signable, SignableField};
+pub use self::signature::{
+    compute_challenge, recover_key_from_nonce_reuse, verify_equation, Signature,
+};
+pub use self::structure::StructureProof;
+pub use self::transcript::{
+    hash_to_scalar, TranscriptProtocol, ABSTAIN_LABEL, ABSTAIN_TOKEN, CACHE_DIGEST_LABEL,
+    CHALLENGE_FN_TRANSCRIPT_LABEL, CHALLENGE_LABEL, COMMITMENTS_TRANSCRIPT_LABEL,
+    COMMITMENT_COUNT_LABEL, COMMITMENT_INDEX_LABEL, COMMITMENT_TRANSCRIPT_LABEL,
+    COUNTERSIGN_TRANSCRIPT_LABEL, COUNTER_LABEL, COUNTER_TRANSCRIPT_LABEL, DOMAIN_SEP_LABEL,
+    FINGERPRINT_TRANSCRIPT_LABEL, G_LABEL, HASH_TO_SCALAR_TRANSCRIPT_LABEL, H_LABEL,
+    KEY_AGGREGATION_LABEL, KEY_COEFFICIENT_LABEL, KEY_COMMITTING_TRANSCRIPT_LABEL,
+    KEY_ROTATION_TRANSCRIPT_LABEL, KEY_STRUCTURE_TRANSCRIPT_LABEL, MESSAGE_CHUNK_LEN,
+    MESSAGE_TRANSCRIPT_LABEL, PREHASH_ALGORITHM_LABEL, PREHASH_TRANSCRIPT_LABEL, PROTOCOL_ID_LABEL,
+    PROTOCOL_TRANSCRIPT_LABEL, READER_TRANSCRIPT_LABEL, REQUEST_BODY_LABEL, REQUEST_METHOD_LABEL,
+    REQUEST_TRANSCRIPT_LABEL, R_LABEL, SESSION_ID_LABEL, SHORT_SIGNING_DOMAIN,
+    SIGNABLE_TRANSCRIPT_LABEL, SIGNING_DOMAIN,
+};
+pub use self::verified::Verified;
+#[cfg(feature = "derive")]
+pub use zkschnorr_derive::Signable;
 
+#[doc(hidden)]
+pub mod __private {
+    pub use merlin::Transcript;
+}
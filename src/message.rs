@@ -0,0 +1,65 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::MESSAGE_TRANSCRIPT_LABEL;
+
+/// Multi-field message, collected as `(label, bytes)` pairs.
+///
+/// Signing and verifying build their transcripts from the builder with the same code,
+/// so the two sides cannot append the fields differently. The transcript is labelled
+/// "Elgamal.sign_message" and the fields are appended in order, so a builder with a
+/// single field is equivalent to `Signature::sign_message` with that label and message.
+#[derive(Clone, Default, Debug)]
+pub struct MessageBuilder {
+    fields: Vec<(&'static [u8], Vec<u8>)>,
+}
+
+impl MessageBuilder {
+    /// Returns an empty message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a field with the given label.
+    pub fn append(mut self, label: &'static [u8], bytes: impl AsRef<[u8]>) -> Self {
+        self.fields.push((label, bytes.as_ref().to_vec()));
+        self
+    }
+
+    /// Returns the fields added so far.
+    pub fn fields(&self) -> &[(&'static [u8], Vec<u8>)] {
+        &self.fields
+    }
+
+    /// Creates the transcript used for signing and verifying the message.
+    pub fn transcript(&self) -> Transcript {
+        let mut t = Transcript::new(MESSAGE_TRANSCRIPT_LABEL);
+        for (label, bytes) in self.fields.iter() {
+            t.append_message(label, bytes);
+        }
+        t
+    }
+}
+
+impl Signature {
+    /// Signs a multi-field message built with `MessageBuilder`.
+    pub fn sign_message_builder(
+        message: &MessageBuilder,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(&mut message.transcript(), pubkey, privkey)
+    }
+
+    /// Verifies a signature created with `sign_message_builder` over the same message.
+    pub fn verify_message_builder(
+        &self,
+        message: &MessageBuilder,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(&mut message.transcript(), pubkey)
+    }
+}
@@ -0,0 +1,34 @@
+//! Profiling counters, enabled with the `metrics` feature.
+//!
+//! These are meant for profiling verification-heavy code (e.g. to confirm that
+//! caching actually reduces work), not for production monitoring. Without the
+//! feature the counting compiles away entirely.
+
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+
+#[cfg(feature = "metrics")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "metrics")]
+static DECOMPRESSIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the number of Ristretto point decompressions performed by this crate
+/// since the start of the process (or the last `reset_decompression_count`).
+#[cfg(feature = "metrics")]
+pub fn decompression_count() -> u64 {
+    DECOMPRESSIONS.load(Ordering::Relaxed)
+}
+
+/// Resets the decompression counter to zero.
+#[cfg(feature = "metrics")]
+pub fn reset_decompression_count() {
+    DECOMPRESSIONS.store(0, Ordering::Relaxed);
+}
+
+/// Decompresses a point, counting the call when the `metrics` feature is enabled.
+#[inline]
+pub(crate) fn decompress(point: &CompressedRistretto) -> Option<RistrettoPoint> {
+    #[cfg(feature = "metrics")]
+    DECOMPRESSIONS.fetch_add(1, Ordering::Relaxed);
+    point.decompress()
+}
@@ -0,0 +1,151 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+#[cfg(feature = "prehash")]
+use sha2::{Digest, Sha256, Sha512};
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+#[cfg(feature = "prehash")]
+use super::transcript::PREHASH_ALGORITHM_LABEL;
+use super::transcript::PREHASH_TRANSCRIPT_LABEL;
+
+/// Streaming SHA-512 hasher producing the digest expected by `Signature::sign_prehashed`.
+///
+/// Large inputs, such as files, can be fed in chunks with `update` (or through
+/// `std::io::Write`, e.g. with `std::io::copy`) without being held in memory.
+#[cfg(feature = "prehash")]
+#[derive(Clone, Default)]
+pub struct Hasher(Sha512);
+
+#[cfg(feature = "prehash")]
+impl Hasher {
+    /// Creates a hasher over an empty input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next chunk of the input.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.0.update(chunk);
+    }
+
+    /// Returns the SHA-512 digest of all chunks fed so far.
+    pub fn finalize(self) -> [u8; 64] {
+        let mut digest = [0u8; 64];
+        digest.copy_from_slice(&self.0.finalize());
+        digest
+    }
+}
+
+#[cfg(feature = "prehash")]
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Digest algorithm usable with `Signature::sign_prehashed_with`.
+///
+/// The identifier is bound into the transcript next to the digest, so a signature
+/// over a digest computed with one algorithm never verifies as a digest of another.
+#[cfg(feature = "prehash")]
+pub trait PrehashDigest: Digest {
+    /// Identifier of the algorithm, unique among the implementations in use.
+    const ALGORITHM_ID: &'static [u8];
+}
+
+#[cfg(feature = "prehash")]
+impl PrehashDigest for Sha256 {
+    const ALGORITHM_ID: &'static [u8] = b"sha256";
+}
+
+#[cfg(feature = "prehash")]
+impl PrehashDigest for Sha512 {
+    const ALGORITHM_ID: &'static [u8] = b"sha512";
+}
+
+impl Signature {
+    /// Signs the 64-byte digest of a message computed outside the transcript,
+    /// e.g. with `Hasher` (SHA-512). Internally it creates a Transcript instance
+    /// labelled "ZkSchnorr.sign_prehashed", and appends to it the digest labelled
+    /// with a user-provided `label`. The signature is only as strong as the
+    /// collision resistance of the hash function that produced the digest.
+    pub fn sign_prehashed(
+        label: &'static [u8],
+        digest: &[u8; 64],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut Self::transcript_for_digest(label, digest),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_prehashed` over the same digest.
+    pub fn verify_prehashed(
+        &self,
+        label: &'static [u8],
+        digest: &[u8; 64],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(&mut Self::transcript_for_digest(label, digest), pubkey)
+    }
+
+    /// Hashes a message with the digest algorithm `D` and signs the digest.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_prehashed",
+    /// appends to it `D::ALGORITHM_ID` labelled "prehash", and then the digest
+    /// labelled with a user-provided `label`.
+    #[cfg(feature = "prehash")]
+    pub fn sign_prehashed_with<D: PrehashDigest>(
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut Self::transcript_for_algorithm::<D>(label, message),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_prehashed_with` using the same
+    /// digest algorithm `D`.
+    #[cfg(feature = "prehash")]
+    pub fn verify_prehashed_with<D: PrehashDigest>(
+        &self,
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(
+            &mut Self::transcript_for_algorithm::<D>(label, message),
+            pubkey,
+        )
+    }
+
+    #[cfg(feature = "prehash")]
+    fn transcript_for_algorithm<D: PrehashDigest>(
+        label: &'static [u8],
+        message: &[u8],
+    ) -> Transcript {
+        let mut t = Transcript::new(PREHASH_TRANSCRIPT_LABEL);
+        t.append_message(PREHASH_ALGORITHM_LABEL, D::ALGORITHM_ID);
+        t.append_message(label, &D::digest(message));
+        t
+    }
+
+    fn transcript_for_digest(label: &'static [u8], digest: &[u8; 64]) -> Transcript {
+        let mut t = Transcript::new(PREHASH_TRANSCRIPT_LABEL);
+        t.append_message(label, digest);
+        t
+    }
+}
@@ -0,0 +1,68 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::{PROTOCOL_ID_LABEL, PROTOCOL_TRANSCRIPT_LABEL};
+
+/// Identifier of the protocol a signature is made for.
+///
+/// Signatures created with `Signature::sign_in_protocol` are bound to the identifier,
+/// so a signature made for one protocol never verifies in another,
+/// even over the same label and message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProtocolId(&'static [u8]);
+
+impl ProtocolId {
+    /// Creates a protocol identifier from its byte string, e.g. `b"payments v2"`.
+    pub const fn new(id: &'static [u8]) -> Self {
+        ProtocolId(id)
+    }
+
+    /// Returns the identifier bytes.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        self.0
+    }
+
+    fn transcript_for_message(&self, label: &'static [u8], message: &[u8]) -> Transcript {
+        let mut t = Transcript::new(PROTOCOL_TRANSCRIPT_LABEL);
+        t.append_message(PROTOCOL_ID_LABEL, self.0);
+        t.append_message(label, message);
+        t
+    }
+}
+
+impl Signature {
+    /// Signs a message on behalf of the protocol `protocol_id`.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_in_protocol",
+    /// appends the protocol identifier labelled "protocol-id", and then the
+    /// message bytes labelled with a user-provided `label`.
+    pub fn sign_in_protocol(
+        protocol_id: ProtocolId,
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut protocol_id.transcript_for_message(label, message),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_in_protocol` for the same protocol.
+    pub fn verify_in_protocol(
+        &self,
+        protocol_id: ProtocolId,
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(
+            &mut protocol_id.transcript_for_message(label, message),
+            pubkey,
+        )
+    }
+}
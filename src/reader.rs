@@ -0,0 +1,74 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use std::io::{self, Read};
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::{MESSAGE_CHUNK_LEN, READER_TRANSCRIPT_LABEL};
+
+impl Signature {
+    /// Signs a message read from `reader` without holding it in memory.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_message_reader",
+    /// and appends the message in chunks of `MESSAGE_CHUNK_LEN` bytes: each chunk is
+    /// preceded by its index (labelled "chunk") and labelled with a user-provided `label`,
+    /// and the number of chunks (labelled "chunks") is appended last. The chunks do not
+    /// depend on how many bytes each read returns, so the signature only depends on the
+    /// message bytes. It does not verify with `verify_message`.
+    pub fn sign_message_reader<R: Read>(
+        label: &'static [u8],
+        reader: R,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> io::Result<Signature> {
+        let mut transcript = transcript_for_reader(label, reader)?;
+        Ok(Self::sign(&mut transcript, pubkey, privkey))
+    }
+
+    /// Verifies a signature created with `sign_message_reader` over the message read
+    /// from `reader`. Fails with `MessageRead` if reading fails.
+    pub fn verify_message_reader<R: Read>(
+        &self,
+        label: &'static [u8],
+        reader: R,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        let mut transcript = transcript_for_reader(label, reader)
+            .map_err(|e| ZkSchnorrError::MessageRead(e.kind()))?;
+        self.verify(&mut transcript, pubkey)
+    }
+}
+
+fn transcript_for_reader<R: Read>(label: &'static [u8], mut reader: R) -> io::Result<Transcript> {
+    let mut t = Transcript::new(READER_TRANSCRIPT_LABEL);
+    let mut chunk = vec![0u8; MESSAGE_CHUNK_LEN];
+    let mut chunks = 0u64;
+    loop {
+        let len = read_chunk(&mut reader, &mut chunk)?;
+        if len == 0 {
+            break;
+        }
+        t.append_u64(b"chunk", chunks);
+        t.append_message(label, &chunk[..len]);
+        chunks += 1;
+        if len < chunk.len() {
+            break;
+        }
+    }
+    t.append_u64(b"chunks", chunks);
+    Ok(t)
+}
+
+/// Fills `buf` from `reader`, returning fewer bytes only at the end of the input.
+fn read_chunk<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
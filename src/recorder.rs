@@ -0,0 +1,195 @@
+//! Transcript recording for debugging mismatches between signer and verifier.
+//!
+//! When the transcripts used for signing and verification diverge, verification
+//! only fails with `InvalidSignature`. Recording the label and length of every
+//! message appended on both sides makes it possible to point at the first difference.
+
+use core::fmt;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use thiserror::Error;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+
+/// Sequence of `(label, length)` pairs of the messages appended to a transcript.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TranscriptLog {
+    label: &'static [u8],
+    appends: Vec<(&'static [u8], usize)>,
+}
+
+impl TranscriptLog {
+    /// Returns the label the transcript was created with.
+    pub fn label(&self) -> &'static [u8] {
+        self.label
+    }
+
+    /// Returns the recorded `(label, length)` pairs in order.
+    pub fn appends(&self) -> &[(&'static [u8], usize)] {
+        &self.appends
+    }
+
+    /// Returns the first difference between this log (the signer's) and `other`
+    /// (the verifier's), or `None` if they match.
+    pub fn first_divergence(&self, other: &TranscriptLog) -> Option<TranscriptDivergence> {
+        if self.label != other.label {
+            return Some(TranscriptDivergence::Label {
+                signer: self.label,
+                verifier: other.label,
+            });
+        }
+        let len = core::cmp::max(self.appends.len(), other.appends.len());
+        (0..len).find_map(|index| {
+            let signer = self.appends.get(index).copied();
+            let verifier = other.appends.get(index).copied();
+            if signer == verifier {
+                None
+            } else {
+                Some(TranscriptDivergence::Append {
+                    index,
+                    signer,
+                    verifier,
+                })
+            }
+        })
+    }
+}
+
+/// Reason why `Signature::verify_recorded` failed.
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum RecordedVerifyError {
+    /// Verification failed and the verifier's transcript diverged from the signer's
+    #[error("Signature verification failed: {0}")]
+    Diverged(TranscriptDivergence),
+
+    /// Verification failed although the transcripts have the same shape
+    #[error(transparent)]
+    Failed(ZkSchnorrError),
+}
+
+impl From<RecordedVerifyError> for ZkSchnorrError {
+    fn from(err: RecordedVerifyError) -> Self {
+        match err {
+            RecordedVerifyError::Diverged(_) => ZkSchnorrError::InvalidSignature,
+            RecordedVerifyError::Failed(err) => err,
+        }
+    }
+}
+
+/// First point where the verifier's transcript departs from the signer's.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TranscriptDivergence {
+    /// The transcripts were created with different labels.
+    Label {
+        /// Label used by the signer
+        signer: &'static [u8],
+        /// Label used by the verifier
+        verifier: &'static [u8],
+    },
+    /// The appends at `index` differ. `None` means that side appended fewer messages.
+    Append {
+        /// Position of the first differing append
+        index: usize,
+        /// Signer's `(label, length)` at `index`
+        signer: Option<(&'static [u8], usize)>,
+        /// Verifier's `(label, length)` at `index`
+        verifier: Option<(&'static [u8], usize)>,
+    },
+}
+
+impl fmt::Display for TranscriptDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let append = |entry: &Option<(&[u8], usize)>| match entry {
+            Some((label, len)) => format!("{:?} ({} bytes)", String::from_utf8_lossy(label), len),
+            None => "nothing".to_string(),
+        };
+        match self {
+            TranscriptDivergence::Label { signer, verifier } => write!(
+                f,
+                "transcript label {:?} differs from signer's {:?}",
+                String::from_utf8_lossy(verifier),
+                String::from_utf8_lossy(signer)
+            ),
+            TranscriptDivergence::Append {
+                index,
+                signer,
+                verifier,
+            } => write!(
+                f,
+                "append #{} is {} but the signer appended {}",
+                index,
+                append(verifier),
+                append(signer)
+            ),
+        }
+    }
+}
+
+/// Merlin transcript that records the label and length of each appended message.
+pub struct RecordingTranscript {
+    transcript: Transcript,
+    log: TranscriptLog,
+}
+
+impl RecordingTranscript {
+    /// Creates a new recording transcript with the given label.
+    pub fn new(label: &'static [u8]) -> Self {
+        Self {
+            transcript: Transcript::new(label),
+            log: TranscriptLog {
+                label,
+                appends: Vec::new(),
+            },
+        }
+    }
+
+    /// Appends a message to the transcript and records its label and length.
+    pub fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.log.appends.push((label, message.len()));
+        self.transcript.append_message(label, message);
+    }
+
+    /// Appends a `u64` to the transcript and records it as an 8-byte message.
+    pub fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.log.appends.push((label, 8));
+        self.transcript.append_u64(label, x);
+    }
+
+    /// Returns the appends recorded so far.
+    pub fn log(&self) -> &TranscriptLog {
+        &self.log
+    }
+}
+
+impl Signature {
+    /// Creates a signature like `sign`, also returning the log of the transcript
+    /// as it was at signing time, to be passed to `verify_recorded`.
+    pub fn sign_recorded(
+        transcript: &mut RecordingTranscript,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> (Signature, TranscriptLog) {
+        let log = transcript.log.clone();
+        (Self::sign(&mut transcript.transcript, pubkey, privkey), log)
+    }
+
+    /// Verifies the signature like `verify`. If verification fails and the
+    /// verifier's transcript diverged from `signer_log`, fails with `Diverged`
+    /// describing the first difference, and otherwise with `Failed` holding the
+    /// error of `verify`.
+    pub fn verify_recorded(
+        &self,
+        transcript: &mut RecordingTranscript,
+        pubkey: VerificationKey,
+        signer_log: &TranscriptLog,
+    ) -> Result<(), RecordedVerifyError> {
+        self.verify(&mut transcript.transcript, pubkey).map_err(|err| {
+            match signer_log.first_divergence(&transcript.log) {
+                Some(divergence) => RecordedVerifyError::Diverged(divergence),
+                None => RecordedVerifyError::Failed(err),
+            }
+        })
+    }
+}
@@ -0,0 +1,51 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::{
+    REQUEST_BODY_LABEL, REQUEST_METHOD_LABEL, REQUEST_TRANSCRIPT_LABEL, SESSION_ID_LABEL,
+};
+
+fn transcript_for_request(session_id: &[u8], method: &[u8], body: &[u8]) -> Transcript {
+    let mut t = Transcript::new(REQUEST_TRANSCRIPT_LABEL);
+    t.append_message(SESSION_ID_LABEL, session_id);
+    t.append_message(REQUEST_METHOD_LABEL, method);
+    t.append_message(REQUEST_BODY_LABEL, body);
+    t
+}
+
+impl Signature {
+    /// Signs an API request bound to `session_id`, a unique session or request ID.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_request" and
+    /// appends the session ID labelled "session-id", the method (e.g. `b"POST /transfer"`)
+    /// labelled "method", and the body labelled "body".
+    ///
+    /// A signature made for one session does not verify for another, so replaying
+    /// it in a different request fails; the server must not accept a session ID twice.
+    pub fn sign_request(
+        session_id: &[u8],
+        method: &[u8],
+        body: &[u8],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut transcript_for_request(session_id, method, body),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_request` for the same session, method and body.
+    pub fn verify_request(
+        &self,
+        session_id: &[u8],
+        method: &[u8],
+        body: &[u8],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(&mut transcript_for_request(session_id, method, body), pubkey)
+    }
+}
@@ -0,0 +1,142 @@
+//! Passphrase-protected storage of signing keys.
+//!
+//! A sealed key is encrypted with ChaCha20-Poly1305 under a key derived from the
+//! passphrase with scrypt. The encoding is
+//! `version (1 byte) || log_n (1 byte) || r (u32 LE) || p (u32 LE) || salt (16 bytes) || nonce (12 bytes) || ciphertext (48 bytes)`,
+//! where the header preceding the nonce is authenticated as associated data.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::scalar::Scalar;
+use rand_core::RngCore;
+use zeroize::Zeroize;
+
+use super::errors::ZkSchnorrError;
+use super::key::{SigningKey, SigningKeyBytes};
+use super::signature::default_rng;
+
+/// Current version tag of the sealed key encoding.
+const SEALED_KEY_VERSION: u8 = 1;
+const HEADER_LEN: usize = 1 + 1 + 4 + 4 + 16;
+const SEALED_KEY_LEN: usize = HEADER_LEN + 12 + 32 + 16;
+/// Largest scrypt `log_n` accepted, so that opening an untrusted sealed key
+/// cannot demand an unbounded amount of memory.
+const MAX_LOG_N: u8 = 20;
+/// Largest scrypt `r * p` accepted, the product of the recommended parameters.
+const MAX_R_P: u64 = 8;
+
+/// Trait for encrypting a signing key at rest with a passphrase.
+pub trait SigningKeySeal: Sized {
+    /// Encrypts the key with a passphrase, using scrypt's recommended
+    /// cost parameters (`log_n = 15`, `r = 8`, `p = 1`).
+    fn seal(&self, passphrase: &[u8]) -> Vec<u8>;
+
+    /// Encrypts the key with a passphrase and explicit scrypt cost parameters,
+    /// which are stored in the output. Fails with `InvalidSealedKey` if the
+    /// parameters are not accepted by scrypt, or exceed `log_n = 20` or `r * p = 8`.
+    fn seal_with_params(
+        &self,
+        passphrase: &[u8],
+        log_n: u8,
+        r: u32,
+        p: u32,
+    ) -> Result<Vec<u8>, ZkSchnorrError>;
+
+    /// Decrypts a key produced by `seal`. Fails with `InvalidSealedKey` if the
+    /// encoding is malformed, the passphrase is wrong or the data was modified.
+    /// The stored scrypt parameters are checked against the limits of
+    /// `seal_with_params` before any key derivation.
+    fn open(sealed: &[u8], passphrase: &[u8]) -> Result<Self, ZkSchnorrError>;
+}
+
+impl SigningKeySeal for SigningKey {
+    fn seal(&self, passphrase: &[u8]) -> Vec<u8> {
+        let params = scrypt::Params::recommended();
+        self.seal_with_params(passphrase, params.log_n(), params.r(), params.p())
+            .expect("recommended scrypt parameters are valid")
+    }
+
+    fn seal_with_params(
+        &self,
+        passphrase: &[u8],
+        log_n: u8,
+        r: u32,
+        p: u32,
+    ) -> Result<Vec<u8>, ZkSchnorrError> {
+        let mut rng = default_rng();
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut nonce);
+
+        let mut sealed = Vec::with_capacity(SEALED_KEY_LEN);
+        sealed.push(SEALED_KEY_VERSION);
+        sealed.push(log_n);
+        sealed.extend_from_slice(&r.to_le_bytes());
+        sealed.extend_from_slice(&p.to_le_bytes());
+        sealed.extend_from_slice(&salt);
+
+        let cipher = derive_cipher(passphrase, &salt, log_n, r, p)?;
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &self.to_scalar_bytes(),
+                    aad: &sealed,
+                },
+            )
+            .map_err(|_| ZkSchnorrError::InvalidSealedKey)?;
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn open(sealed: &[u8], passphrase: &[u8]) -> Result<Self, ZkSchnorrError> {
+        if sealed.len() != SEALED_KEY_LEN || sealed[0] != SEALED_KEY_VERSION {
+            return Err(ZkSchnorrError::InvalidSealedKey);
+        }
+        let (header, rest) = sealed.split_at(HEADER_LEN);
+        let (nonce, ciphertext) = rest.split_at(12);
+
+        let mut u32_buf = [0u8; 4];
+        u32_buf.copy_from_slice(&header[2..6]);
+        let r = u32::from_le_bytes(u32_buf);
+        u32_buf.copy_from_slice(&header[6..10]);
+        let p = u32::from_le_bytes(u32_buf);
+
+        let cipher = derive_cipher(passphrase, &header[10..], header[1], r, p)?;
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| ZkSchnorrError::InvalidSealedKey)?;
+
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&plaintext);
+        plaintext.zeroize();
+        Scalar::from_scalar_bytes(bytes)
+    }
+}
+
+fn derive_cipher(
+    passphrase: &[u8],
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+) -> Result<ChaCha20Poly1305, ZkSchnorrError> {
+    if log_n > MAX_LOG_N || u64::from(r) * u64::from(p) > MAX_R_P {
+        return Err(ZkSchnorrError::InvalidSealedKey);
+    }
+    let params = scrypt::Params::new(log_n, r, p).map_err(|_| ZkSchnorrError::InvalidSealedKey)?;
+    let mut key = [0u8; 32];
+    let derived = scrypt::scrypt(passphrase, salt, &params, &mut key)
+        .map(|_| ChaCha20Poly1305::new(Key::from_slice(&key)))
+        .map_err(|_| ZkSchnorrError::InvalidSealedKey);
+    key.zeroize();
+    derived
+}
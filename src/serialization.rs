@@ -1,34 +1,152 @@
 use curve25519_dalek::ristretto::CompressedRistretto;
 use curve25519_dalek::scalar::Scalar;
-use serde::{de::Deserializer, de::Visitor, ser::Serializer, Deserialize, Serialize};
+#[cfg(feature = "hex")]
+use core::fmt;
+#[cfg(feature = "hex")]
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::de::{Deserializer, SeqAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::{ser::Serializer, Deserialize, Serialize};
+use subtle::{Choice, ConstantTimeEq};
 
-use super::ZkSchnorrError;
+use super::ShortSignature;
 use super::Signature;
+#[cfg(any(feature = "hex", feature = "base32", feature = "armor"))]
+use super::VerificationKey;
+use super::ZkSchnorrError;
+
+/// Length of the `Signature` encoding: the compressed `R` followed by `s`.
+const SIGNATURE_LEN: usize = 64;
+
+// The wire format is `R || s` with no other fields; fail the build if either part changes size.
+const _: () = assert!(
+    core::mem::size_of::<CompressedRistretto>() + core::mem::size_of::<Scalar>() == SIGNATURE_LEN
+);
+
+/// Leading tag of the `ShortSignature` encoding, distinguishing it from full signatures.
+const SHORT_SIGNATURE_TAG: u8 = 0x53;
 
 impl Signature {
-    /// Decodes a signature from a 64-byte slice.
+    /// Decodes a signature from a 64-byte slice. Like `from_parts`, fails with
+    /// `InvalidSignature` if `s` is not canonical or `R` is not a valid point.
     pub fn from_bytes(sig: impl AsRefExt) -> Result<Self, ZkSchnorrError> {
         let sig = sig.as_ref_ext();
-        if sig.len() != 64 {
+        if sig.len() != SIGNATURE_LEN {
             return Err(ZkSchnorrError::InvalidSignature);
         }
         let mut rbuf = [0u8; 32];
         let mut sbuf = [0u8; 32];
         rbuf[..].copy_from_slice(&sig[..32]);
         sbuf[..].copy_from_slice(&sig[32..]);
-        Ok(Signature {
-            R: CompressedRistretto(rbuf),
-            s: Scalar::from_canonical_bytes(sbuf).ok_or(ZkSchnorrError::InvalidSignature)?,
-        })
+        Self::from_parts(CompressedRistretto(rbuf), Scalar::from_bits(sbuf))
     }
 
     /// Encodes the signature as a 64-byte array.
-    pub fn to_bytes(&self) -> [u8; 64] {
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LEN] {
         let mut buf = [0u8; 64];
         buf[..32].copy_from_slice(self.R.as_bytes());
         buf[32..].copy_from_slice(self.s.as_bytes());
         buf
     }
+
+    /// Encodes the signature as a 64-byte array with `s` in big-endian order,
+    /// for interoperability with verifiers that expect big-endian scalars.
+    /// The default encoding (`to_bytes`) is little-endian; `R` is a compressed
+    /// point and is encoded identically in both.
+    pub fn to_bytes_be(&self) -> [u8; SIGNATURE_LEN] {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(self.R.as_bytes());
+        buf[32..].copy_from_slice(&scalar_to_bytes_be(&self.s));
+        buf
+    }
+
+    /// Decodes a signature produced by `to_bytes_be`. Like `from_bytes`, fails with
+    /// `InvalidSignature` if `s` is not canonical or `R` is not a valid point.
+    pub fn from_bytes_be(sig: impl AsRefExt) -> Result<Self, ZkSchnorrError> {
+        let sig = sig.as_ref_ext();
+        if sig.len() != SIGNATURE_LEN {
+            return Err(ZkSchnorrError::InvalidSignature);
+        }
+        let mut rbuf = [0u8; 32];
+        let mut sbuf = [0u8; 32];
+        rbuf[..].copy_from_slice(&sig[..32]);
+        sbuf[..].copy_from_slice(&sig[32..]);
+        let s = scalar_from_bytes_be(sbuf).ok_or(ZkSchnorrError::InvalidSignature)?;
+        Self::from_parts(CompressedRistretto(rbuf), s)
+    }
+
+    /// Brings the signature into its canonical form. Every `Signature` already is in
+    /// that form: signing only produces reduced scalars, and `from_bytes`, `from_bytes_be`
+    /// and `from_parts` reject an unreduced `s` or a non-canonical `R` encoding. This is
+    /// therefore an identity, kept for compatibility with code that normalizes
+    /// signatures before comparing their encodings.
+    pub fn canonicalize(&mut self) {}
+
+    /// Compares the encoded signature with `other_bytes` in constant time.
+    ///
+    /// Signatures are not MACs: anyone can verify them, and a message has many valid
+    /// signatures, one per nonce. But where signature bytes are
+    /// compared as opaque tags, this avoids leaking the position of the first mismatch.
+    /// Only the length of `other_bytes` (which must be 64) is compared in variable time.
+    pub fn ct_eq_bytes(&self, other_bytes: &[u8]) -> Choice {
+        if other_bytes.len() != SIGNATURE_LEN {
+            return Choice::from(0);
+        }
+        self.to_bytes()[..].ct_eq(other_bytes)
+    }
+
+    /// Encodes the signature into the first 64 bytes of `out`, without allocating.
+    /// Fails if `out` is shorter than 64 bytes.
+    pub fn encode_into(&self, out: &mut [u8]) -> Result<(), ZkSchnorrError> {
+        if out.len() < SIGNATURE_LEN {
+            return Err(ZkSchnorrError::BufferTooSmall);
+        }
+        out[..32].copy_from_slice(self.R.as_bytes());
+        out[32..64].copy_from_slice(self.s.as_bytes());
+        Ok(())
+    }
+}
+
+impl ShortSignature {
+    /// Encodes the short signature as `tag (1 byte) || c (16 bytes) || s (32 bytes)`.
+    pub fn to_bytes(&self) -> [u8; 49] {
+        let mut buf = [0u8; 49];
+        buf[0] = SHORT_SIGNATURE_TAG;
+        buf[1..17].copy_from_slice(&self.c);
+        buf[17..].copy_from_slice(self.s.as_bytes());
+        buf
+    }
+
+    /// Decodes a short signature produced by `to_bytes`.
+    /// Fails on a wrong length or tag, or a non-canonical `s`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZkSchnorrError> {
+        if bytes.len() != 49 || bytes[0] != SHORT_SIGNATURE_TAG {
+            return Err(ZkSchnorrError::InvalidSignature);
+        }
+        let mut c = [0u8; 16];
+        let mut sbuf = [0u8; 32];
+        c.copy_from_slice(&bytes[1..17]);
+        sbuf.copy_from_slice(&bytes[17..]);
+        Ok(ShortSignature {
+            c,
+            s: Scalar::from_canonical_bytes(sbuf).ok_or(ZkSchnorrError::InvalidSignature)?,
+        })
+    }
+}
+
+/// Encodes a scalar (e.g. a `SigningKey`) in big-endian order.
+/// `Scalar::to_bytes` and all encodings in this crate are little-endian by default.
+pub fn scalar_to_bytes_be(scalar: &Scalar) -> [u8; 32] {
+    let mut bytes = scalar.to_bytes();
+    bytes.reverse();
+    bytes
+}
+
+/// Decodes a big-endian scalar, returning `None` if it is not canonical.
+pub fn scalar_from_bytes_be(mut bytes: [u8; 32]) -> Option<Scalar> {
+    bytes.reverse();
+    Scalar::from_canonical_bytes(bytes)
 }
 
 /// Same as `AsRef<[u8]>`, but extended to 64-byte array.
@@ -43,7 +161,7 @@ impl AsRefExt for [u8] {
     }
 }
 
-impl<'a> AsRefExt for &'a [u8] {
+impl AsRefExt for &[u8] {
     fn as_ref_ext(&self) -> &[u8] {
         self
     }
@@ -55,37 +173,250 @@ impl AsRefExt for [u8; 64] {
     }
 }
 
-// TBD: serialize in hex in case of a human-readable serializer
+#[cfg(feature = "hex")]
+impl fmt::Display for Signature {
+    /// Formats the signature as lowercase hex of its 64-byte encoding.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+#[cfg(feature = "hex")]
+impl FromStr for Signature {
+    type Err = ZkSchnorrError;
+
+    /// Parses a signature from hex of its 64-byte encoding.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Signature::from_bytes(&hex::decode(s).map_err(|_| ZkSchnorrError::InvalidSignature)?[..])
+    }
+}
+
+#[cfg(feature = "hex")]
+impl fmt::Display for VerificationKey {
+    /// Formats the key as lowercase hex of its 64-byte encoding.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+#[cfg(feature = "hex")]
+impl FromStr for VerificationKey {
+    type Err = ZkSchnorrError;
+
+    /// Parses a key from hex of its 64-byte encoding.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VerificationKey::from_bytes(&hex::decode(s).map_err(|_| ZkSchnorrError::InvalidPoint)?)
+    }
+}
+
+/// Length of the base32 form of a 64-byte encoding: unpadded RFC 4648 base32,
+/// 5 bits per character.
+#[cfg(feature = "base32")]
+pub const BASE32_LEN: usize = 103;
+
+#[cfg(feature = "base32")]
+fn decode_base32(s: &str) -> Option<Vec<u8>> {
+    if s.len() != BASE32_LEN {
+        return None;
+    }
+    data_encoding::BASE32_NOPAD.decode(s.as_bytes()).ok()
+}
+
+#[cfg(feature = "base32")]
+impl Signature {
+    /// Encodes the signature as uppercase unpadded base32 of its 64-byte encoding
+    /// (`BASE32_LEN` characters), which fits the QR alphanumeric mode.
+    pub fn to_base32(&self) -> String {
+        data_encoding::BASE32_NOPAD.encode(&self.to_bytes())
+    }
+
+    /// Decodes a signature produced by `to_base32`.
+    /// Fails with `InvalidSignature` if the string is not exactly `BASE32_LEN`
+    /// uppercase base32 characters.
+    pub fn from_base32(s: &str) -> Result<Self, ZkSchnorrError> {
+        Signature::from_bytes(&decode_base32(s).ok_or(ZkSchnorrError::InvalidSignature)?[..])
+    }
+}
+
+#[cfg(feature = "base32")]
+impl VerificationKey {
+    /// Encodes the key as uppercase unpadded base32 of its 64-byte encoding
+    /// (`BASE32_LEN` characters), which fits the QR alphanumeric mode.
+    pub fn to_base32(&self) -> String {
+        data_encoding::BASE32_NOPAD.encode(&self.to_bytes())
+    }
+
+    /// Decodes a key produced by `to_base32`.
+    /// Fails with `InvalidPoint` if the string is not exactly `BASE32_LEN`
+    /// uppercase base32 characters or does not encode a valid key.
+    pub fn from_base32(s: &str) -> Result<Self, ZkSchnorrError> {
+        VerificationKey::from_bytes(&decode_base32(s).ok_or(ZkSchnorrError::InvalidPoint)?)
+    }
+}
+
+/// Length of the base64 body of an armored key: 64 bytes with padding.
+#[cfg(feature = "armor")]
+const ARMOR_BODY_LEN: usize = 88;
+
+/// Line width of the armored body, as in PEM.
+#[cfg(feature = "armor")]
+const ARMOR_LINE_LEN: usize = 64;
+
+#[cfg(feature = "armor")]
+impl VerificationKey {
+    /// Encodes the key as an armored text block: a `-----BEGIN <label>-----` line,
+    /// the base64 of its 64-byte encoding wrapped at 64 columns, and a matching
+    /// `-----END <label>-----` line, e.g. with the label `ZKSCHNORR VERIFICATION KEY`.
+    /// The label should be uppercase ASCII without hyphens, as in PEM.
+    pub fn to_armored(&self, label: &str) -> String {
+        let body = data_encoding::BASE64.encode(&self.to_bytes());
+        let mut out = format!("-----BEGIN {}-----\n", label);
+        for line in body.as_bytes().chunks(ARMOR_LINE_LEN) {
+            out.push_str(core::str::from_utf8(line).expect("base64 is ASCII"));
+            out.push('\n');
+        }
+        out.push_str(&format!("-----END {}-----\n", label));
+        out
+    }
+
+    /// Decodes a key produced by `to_armored` with the same `label`.
+    /// Surrounding whitespace and the body's line breaks are ignored. Fails with
+    /// `InvalidArmor` if the header or footer does not match `label` or the body is
+    /// not 88 characters of base64, and with `InvalidPoint` if it is not a valid key.
+    pub fn from_armored(text: &str, label: &str) -> Result<Self, ZkSchnorrError> {
+        let mut lines = text.trim().lines().map(str::trim);
+        if lines.next() != Some(format!("-----BEGIN {}-----", label).as_str())
+            || lines.next_back() != Some(format!("-----END {}-----", label).as_str())
+        {
+            return Err(ZkSchnorrError::InvalidArmor);
+        }
+        let body: String = lines.collect();
+        if body.len() != ARMOR_BODY_LEN {
+            return Err(ZkSchnorrError::InvalidArmor);
+        }
+        let bytes = data_encoding::BASE64
+            .decode(body.as_bytes())
+            .map_err(|_| ZkSchnorrError::InvalidArmor)?;
+        VerificationKey::from_bytes(&bytes)
+    }
+}
+
+// Human-readable serializers (e.g. JSON) use the hex string form, which also makes the
+// types compose with `serde_with::DisplayFromStr`; binary serializers use raw bytes.
+// Deserialization accepts either form, as well as a sequence of bytes.
+#[cfg(feature = "serde")]
 impl Serialize for Signature {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_bytes(&self.to_bytes()[..])
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.to_bytes()[..])
+        }
     }
 }
+
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Signature {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        struct SigVisitor;
+        deserialize_encoded(
+            deserializer,
+            EncodedVisitor {
+                expecting: "a valid schnorr signature",
+                decode: |bytes| Signature::from_bytes(bytes),
+            },
+        )
+    }
+}
 
-        impl<'de> Visitor<'de> for SigVisitor {
-            type Value = Signature;
+#[cfg(feature = "serde")]
+impl Serialize for VerificationKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+}
 
-            fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                formatter.write_str("a valid schnorr signature")
-            }
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for VerificationKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_encoded(
+            deserializer,
+            EncodedVisitor {
+                expecting: "a valid verification key",
+                decode: VerificationKey::from_bytes,
+            },
+        )
+    }
+}
 
-            fn visit_bytes<E>(self, v: &[u8]) -> Result<Signature, E>
-            where
-                E: serde::de::Error,
-            {
-                Signature::from_bytes(v).map_err(serde::de::Error::custom)
-            }
-        }
+#[cfg(feature = "serde")]
+fn deserialize_encoded<'de, D, T>(
+    deserializer: D,
+    visitor: EncodedVisitor<T>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+/// Visitor for a type encoded as bytes, or as a hex string in human-readable formats.
+#[cfg(feature = "serde")]
+struct EncodedVisitor<T> {
+    expecting: &'static str,
+    decode: fn(&[u8]) -> Result<T, ZkSchnorrError>,
+}
 
-        deserializer.deserialize_bytes(SigVisitor)
+#[cfg(feature = "serde")]
+impl<'de, T> Visitor<'de> for EncodedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<T, E>
+    where
+        E: serde::de::Error,
+    {
+        (self.decode)(v).map_err(serde::de::Error::custom)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: serde::de::Error,
+    {
+        let bytes = hex::decode(v).map_err(serde::de::Error::custom)?;
+        self.visit_bytes(&bytes)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<T, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(64);
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
     }
 }
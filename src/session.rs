@@ -0,0 +1,103 @@
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::key::VerificationKey;
+use super::metrics::decompress;
+use super::signature::{compute_challenge, default_rng, Signature};
+
+/// Signing split into a commit step, which fixes the nonce commitment `R`, and a
+/// finalize step, which produces the signature. This suits protocols that send `R`
+/// to a peer (see `Signature::verify_committed`) and may pause before finishing.
+///
+/// The session holds the challenge derived from the transcript at commit time, so
+/// the transcript is not needed to finalize. It also holds the secret nonce: anyone
+/// who learns it together with the signature can recover the private key, and
+/// finalizing two copies of the same session with different keys or after the
+/// challenge changed leaks the key as well. `finalize` consumes the session, but
+/// persisted copies must be deleted once used and never restored twice.
+///
+/// For the same reason the session is deliberately not `Clone`: a copy would make it
+/// possible to finalize the same nonce twice. Its public part, the key and `R`, is
+/// available as the copyable `SessionCommitment` (see `public`).
+///
+/// With the `dangerous-session-serde` feature the session implements serde's
+/// `Serialize` and `Deserialize`, writing the nonce in the clear; encrypt the
+/// result before storing it.
+#[cfg_attr(
+    feature = "dangerous-session-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct SigningSession {
+    pubkey: VerificationKey,
+    R: CompressedRistretto,
+    challenge: Scalar,
+    nonce: Scalar,
+}
+
+impl SigningSession {
+    /// Commits to a fresh nonce for signing the transcript with `privkey`.
+    /// Transcript should be in the state `sign` would receive it; it is advanced
+    /// the same way, so the resulting signature verifies with `Signature::verify`.
+    pub fn commit(transcript: &mut Transcript, pubkey: VerificationKey, privkey: &Scalar) -> Self {
+        let mut rng = transcript
+            .build_rng()
+            .rekey_with_witness_bytes(b"x", &privkey.to_bytes())
+            .finalize(&mut default_rng());
+        let nonce = Scalar::random(&mut rng);
+        let R = (decompress(&pubkey.g).unwrap() * nonce).compress();
+        let challenge = compute_challenge(transcript, &pubkey, &R);
+        Self {
+            pubkey,
+            R,
+            challenge,
+            nonce,
+        }
+    }
+
+    /// Returns the nonce commitment `R` to be sent to the peer.
+    pub fn commitment(&self) -> CompressedRistretto {
+        self.R
+    }
+
+    /// Returns the verification key the session signs for.
+    pub fn pubkey(&self) -> VerificationKey {
+        self.pubkey
+    }
+
+    /// Returns the public state of the session, which can be copied and shared freely.
+    pub fn public(&self) -> SessionCommitment {
+        SessionCommitment {
+            pubkey: self.pubkey,
+            R: self.R,
+        }
+    }
+
+    /// Completes the signature with the private key used in `commit`.
+    pub fn finalize(self, privkey: &Scalar) -> Signature {
+        Signature {
+            s: self.nonce + self.challenge * privkey,
+            R: self.R,
+        }
+    }
+}
+
+/// Public state of a `SigningSession`: the verification key and the nonce commitment `R`.
+/// Unlike the session, it holds no secret and is `Copy`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SessionCommitment {
+    pubkey: VerificationKey,
+    R: CompressedRistretto,
+}
+
+impl SessionCommitment {
+    /// Returns the nonce commitment `R` to be sent to the peer.
+    pub fn commitment(&self) -> CompressedRistretto {
+        self.R
+    }
+
+    /// Returns the verification key the session signs for.
+    pub fn pubkey(&self) -> VerificationKey {
+        self.pubkey
+    }
+}
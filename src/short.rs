@@ -0,0 +1,102 @@
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::VartimeMultiscalarMul;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::metrics::decompress;
+use super::signature::{default_rng, Signature};
+use super::transcript::{
+    TranscriptProtocol, CHALLENGE_LABEL, DOMAIN_SEP_LABEL, G_LABEL, H_LABEL, R_LABEL,
+    SHORT_SIGNING_DOMAIN,
+};
+
+/// Compact signature carrying a 16-byte challenge `c` instead of the nonce commitment `R`.
+///
+/// It encodes to 49 bytes (a tag byte, `c` and the 32-byte scalar `s`), instead of
+/// 64 bytes for a `Signature`. The verifier recomputes `R = s·G - c·H` and checks that it hashes
+/// back to `c`. With a 128-bit challenge, a forger succeeds with probability about
+/// 2^-128 per attempt, so short signatures offer about 128-bit security, the same
+/// level as the Ristretto255 group itself (about 2^126 work for a discrete logarithm).
+/// Short signatures cannot be batch-verified.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ShortSignature {
+    /// Truncated Fiat-Shamir challenge
+    pub c: [u8; 16],
+    /// Signature scalar `r + c·x`
+    pub s: Scalar,
+}
+
+impl Signature {
+    /// Creates a short signature for the transcript and key.
+    /// Uses a domain separator distinct from `sign`, so a short signature
+    /// and a full signature never share a challenge.
+    pub fn sign_short(
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> ShortSignature {
+        let mut rng = transcript
+            .build_rng()
+            .rekey_with_witness_bytes(b"x", &privkey.to_bytes())
+            .finalize(&mut default_rng());
+
+        let r = Scalar::random(&mut rng);
+        let R = (decompress(&pubkey.g).unwrap() * r).compress();
+
+        let c = short_challenge(transcript, &pubkey, &R);
+        ShortSignature {
+            c,
+            s: r + challenge_scalar(&c) * privkey,
+        }
+    }
+}
+
+impl ShortSignature {
+    /// Verifies the short signature over a transcript using the provided verification key.
+    /// Transcript should be in the same state as it was during the `sign_short` call
+    /// that created the signature.
+    pub fn verify_short(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        let (g, h) = match (decompress(&pubkey.g), decompress(&pubkey.h)) {
+            (Some(g), Some(h)) => (g, h),
+            _ => return Err(ZkSchnorrError::InvalidSignature),
+        };
+        // `R = s·G - c·H`
+        let R = RistrettoPoint::vartime_multiscalar_mul(
+            &[self.s, -challenge_scalar(&self.c)],
+            &[g, h],
+        )
+        .compress();
+
+        if short_challenge(transcript, &pubkey, &R) == self.c {
+            Ok(())
+        } else {
+            Err(ZkSchnorrError::InvalidSignature)
+        }
+    }
+}
+
+fn short_challenge(
+    transcript: &mut Transcript,
+    pubkey: &VerificationKey,
+    R: &CompressedRistretto,
+) -> [u8; 16] {
+    transcript.append_message(DOMAIN_SEP_LABEL, SHORT_SIGNING_DOMAIN);
+    transcript.append_point(G_LABEL, &pubkey.g);
+    transcript.append_point(H_LABEL, &pubkey.h);
+    transcript.append_point(R_LABEL, R);
+    let mut c = [0u8; 16];
+    transcript.challenge_bytes(CHALLENGE_LABEL, &mut c);
+    c
+}
+
+fn challenge_scalar(c: &[u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(c);
+    Scalar::from_bits(bytes)
+}
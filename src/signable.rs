@@ -0,0 +1,130 @@
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+use super::transcript::{TranscriptProtocol, SIGNABLE_TRANSCRIPT_LABEL};
+
+/// Structured data that can be signed by appending its contents to a transcript.
+///
+/// Usually derived with `#[derive(Signable)]` (with the `derive` feature), which
+/// appends every field labelled with its name, so that signing and verifying
+/// build the transcript with the same code.
+pub trait Signable {
+    /// Appends the value to the transcript.
+    fn to_transcript(&self, t: &mut Transcript);
+}
+
+/// A field type of a `Signable` struct.
+pub trait SignableField {
+    /// Appends the field to the transcript under `label`.
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript);
+}
+
+/// Nested structs are appended as an empty message labelled with the field name,
+/// followed by their own fields.
+impl<T: Signable + ?Sized> SignableField for T {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, b"");
+        self.to_transcript(t);
+    }
+}
+
+macro_rules! impl_signable_field_for_int {
+    ($($t:ty),*) => {
+        $(
+            /// Integers are appended in little-endian order.
+            impl SignableField for $t {
+                fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+                    t.append_message(label, &self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_signable_field_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl SignableField for bool {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, &[*self as u8]);
+    }
+}
+
+impl SignableField for [u8] {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, self);
+    }
+}
+
+impl<const N: usize> SignableField for [u8; N] {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, self);
+    }
+}
+
+impl SignableField for Vec<u8> {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, self);
+    }
+}
+
+impl SignableField for str {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, self.as_bytes());
+    }
+}
+
+impl SignableField for String {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, self.as_bytes());
+    }
+}
+
+impl SignableField for Scalar {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_scalar(label, self);
+    }
+}
+
+impl SignableField for CompressedRistretto {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_point(label, self);
+    }
+}
+
+impl SignableField for VerificationKey {
+    fn append_field(&self, label: &'static [u8], t: &mut Transcript) {
+        t.append_message(label, &self.to_bytes_array());
+    }
+}
+
+impl Signature {
+    /// Signs structured data.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_signable"
+    /// (`SIGNABLE_TRANSCRIPT_LABEL`), and appends the value to it with `Signable::to_transcript`.
+    pub fn sign_signable<T: Signable + ?Sized>(
+        value: &T,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(&mut transcript_for_signable(value), pubkey, privkey)
+    }
+
+    /// Verifies a signature created with `sign_signable` over the same value.
+    pub fn verify_signable<T: Signable + ?Sized>(
+        &self,
+        value: &T,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(&mut transcript_for_signable(value), pubkey)
+    }
+}
+
+fn transcript_for_signable<T: Signable + ?Sized>(value: &T) -> Transcript {
+    let mut t = Transcript::new(SIGNABLE_TRANSCRIPT_LABEL);
+    value.to_transcript(&mut t);
+    t
+}
@@ -1,63 +1,426 @@
-use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, IsIdentity, VartimeMultiscalarMul};
 use std::fmt;
+#[cfg(feature = "timed")]
+use std::time::{Duration, Instant};
 
-use super::batch::{BatchVerification, SingleVerifier};
-use super::errors::ZkSchnorrError;
-use super::key::VerificationKey;
-use super::transcript::TranscriptProtocol;
+use super::batch::{BatchVerification, Verifier};
+use super::errors::{VerifyFailure, ZkSchnorrError};
+use super::key::{PreparedVerificationKey, VerificationKey};
+use super::metrics::decompress;
+use super::transcript::{
+    TranscriptProtocol, CHALLENGE_FN_TRANSCRIPT_LABEL, CHALLENGE_LABEL,
+    COMMITMENT_TRANSCRIPT_LABEL, G_LABEL, H_LABEL, KEY_COMMITTING_TRANSCRIPT_LABEL,
+    MESSAGE_TRANSCRIPT_LABEL, R_LABEL,
+};
 use core::iter;
 use merlin::Transcript;
+use rand_core::{CryptoRng, RngCore};
 
 /// A Schnorr signature.
+///
+/// The fields are private: signatures are only obtained by signing or through the
+/// validating constructors `from_parts` and `from_bytes`, and are read with `s()`
+/// and `R()`.
 #[derive(Copy, Clone)]
 pub struct Signature {
     /// Signature using nonce, message, and private key
-    pub s: Scalar,
+    pub(crate) s: Scalar,
     /// Nonce commitment
-    pub R: CompressedRistretto,
+    pub(crate) R: CompressedRistretto,
 }
 
 impl Signature {
-    /// Creates a signature for a single private key and single message
+    /// Returns the signature scalar `s`.
+    pub fn s(&self) -> Scalar {
+        self.s
+    }
+
+    /// Returns the nonce commitment `R`.
+    pub fn R(&self) -> CompressedRistretto {
+        self.R
+    }
+
+    /// Creates a signature for a single private key and single message.
+    /// The nonce is seeded from `rand::thread_rng()`, or from the `getrandom`
+    /// system RNG when the `getrandom` feature is enabled.
     pub fn sign(
         transcript: &mut Transcript,
         pubkey: VerificationKey,
         privkey: Scalar,
+    ) -> Signature {
+        Self::sign_with_rng(transcript, pubkey, privkey, &mut default_rng())
+    }
+
+    /// Creates a signature for a single private key and single message,
+    /// using `rng` as the external source of randomness for the nonce.
+    /// The nonce is still bound to the transcript and the private key,
+    /// so a seeded `rng` yields reproducible signatures (e.g. for test vectors).
+    pub fn sign_with_rng<T: RngCore + CryptoRng>(
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+        rng: &mut T,
+    ) -> Signature {
+        let g = decompress(&pubkey.g).unwrap();
+        Self::sign_with_generator(transcript, &pubkey, &g, privkey, rng)
+    }
+
+    /// Creates a signature over a transcript that starts with a shared `prefix`
+    /// (e.g. a protocol header common to many messages). The prefix is cloned and
+    /// `suffix_append` appends only the per-message part, so the prefix is absorbed
+    /// once rather than for every message. The result verifies like a signature over
+    /// a transcript built from scratch with the same appends.
+    pub fn sign_with_prefix<F>(
+        prefix: &Transcript,
+        suffix_append: F,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature
+    where
+        F: FnOnce(&mut Transcript),
+    {
+        let mut transcript = prefix.clone();
+        suffix_append(&mut transcript);
+        Self::sign(&mut transcript, pubkey, privkey)
+    }
+
+    /// Verifies a signature over the `prefix` transcript extended by `suffix_append`,
+    /// the counterpart of `sign_with_prefix`.
+    pub fn verify_with_prefix<F>(
+        &self,
+        prefix: &Transcript,
+        suffix_append: F,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError>
+    where
+        F: FnOnce(&mut Transcript),
+    {
+        let mut transcript = prefix.clone();
+        suffix_append(&mut transcript);
+        self.verify(&mut transcript, pubkey)
+    }
+
+    /// Creates a signature like `sign`, using the already decompressed points of `pubkey`.
+    pub fn sign_prepared(
+        transcript: &mut Transcript,
+        pubkey: &PreparedVerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign_with_generator(
+            transcript,
+            &pubkey.key(),
+            pubkey.points().0,
+            privkey,
+            &mut default_rng(),
+        )
+    }
+
+    fn sign_with_generator<T: RngCore + CryptoRng>(
+        transcript: &mut Transcript,
+        pubkey: &VerificationKey,
+        g: &RistrettoPoint,
+        privkey: Scalar,
+        rng: &mut T,
     ) -> Signature {
         let mut rng = transcript
             .build_rng()
             .rekey_with_witness_bytes(b"x", &privkey.to_bytes())
-            .finalize(&mut rand::thread_rng());
+            .finalize(rng);
 
         // Generate ephemeral keypair (r, R). r is a random nonce.
         let r = Scalar::random(&mut rng);
-        // R = generator * r
-        let R = (&pubkey.g.decompress().unwrap() * &r).compress();
-
-        let c = {
-            transcript.zkschnorr_domain_sep();
-            transcript.append_point(b"G", &pubkey.g);
-            transcript.append_point(b"H", &pubkey.h);
-            transcript.append_point(b"R", &R);
-            transcript.challenge_scalar(b"challenge")
+        // R = generator * r, using the precomputed table for the standard base point.
+        let R = if pubkey.uses_standard_base() {
+            (&r * &RISTRETTO_BASEPOINT_TABLE).compress()
+        } else {
+            (g * r).compress()
         };
 
+        let c = compute_challenge(transcript, pubkey, &R);
+
         let s = r + c * privkey;
 
         Signature { s, R }
     }
 
-    
     /// Verifies the signature over a transcript using the provided verification key.
     /// Transcript should be in the same state as it was during the `sign` call
     /// that created the signature.
+    ///
+    /// Both `sign` and `verify` leave the transcript in the same state: the domain
+    /// separator, `G`, `H` and `R` are appended and the `challenge` scalar is squeezed.
+    /// Regardless of the outcome, the transcript is left in this state.
     pub fn verify(
         &self,
         transcript: &mut Transcript,
         pubkey: VerificationKey,
     ) -> Result<(), ZkSchnorrError> {
-        SingleVerifier::verify(|verifier| self.verify_batched(transcript, pubkey, verifier))
+        let c = compute_challenge(transcript, &pubkey, &self.R);
+        match (decompress(&pubkey.g), decompress(&pubkey.h)) {
+            (Some(g), Some(h)) => self.check_equation(&c, &g, &h),
+            _ => Err(ZkSchnorrError::InvalidSignature),
+        }
+    }
+
+    /// Verifies the signature like `verify`, additionally failing with `IdentityPoint`
+    /// if `G`, `H` or `R` decompresses to the identity, which indicates a degenerate
+    /// key or signature.
+    ///
+    /// Ristretto points have no small-order components, so for keys and signatures
+    /// produced by this crate the check is redundant; it is meant as a defensive
+    /// option at trust boundaries where encodings come from untrusted sources.
+    /// The transcript is left in the same state as with `verify`.
+    pub fn verify_strict(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        let c = compute_challenge(transcript, &pubkey, &self.R);
+        let (g, h, R) = match (decompress(&pubkey.g), decompress(&pubkey.h), decompress(&self.R)) {
+            (Some(g), Some(h), Some(R)) => (g, h, R),
+            _ => return Err(ZkSchnorrError::InvalidSignature),
+        };
+        if g.is_identity() || h.is_identity() || R.is_identity() {
+            return Err(ZkSchnorrError::IdentityPoint);
+        }
+        if self.is_degenerate() {
+            return Err(ZkSchnorrError::DegenerateSignature);
+        }
+        if verify_equation(&self.s, &R, &c, &g, &h) {
+            Ok(())
+        } else {
+            Err(ZkSchnorrError::InvalidSignature)
+        }
+    }
+
+    /// Verifies the signature like `verify`, but reports why verification failed:
+    /// a malformed key or `R`, a non-canonical `s`, a degenerate signature, or an
+    /// equation that does not hold. The checks run in that order and the first failing
+    /// one is reported.
+    pub fn verify_detailed(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), VerifyFailure> {
+        let c = compute_challenge(transcript, &pubkey, &self.R);
+        let (g, h) = match (decompress(&pubkey.g), decompress(&pubkey.h)) {
+            (Some(g), Some(h)) => (g, h),
+            _ => return Err(VerifyFailure::MalformedKey),
+        };
+        let R = decompress(&self.R).ok_or(VerifyFailure::MalformedR)?;
+        if !self.s.is_canonical() {
+            return Err(VerifyFailure::NonCanonicalScalar);
+        }
+        if self.is_degenerate() {
+            return Err(VerifyFailure::Degenerate);
+        }
+        if verify_equation(&self.s, &R, &c, &g, &h) {
+            Ok(())
+        } else {
+            Err(VerifyFailure::EquationMismatch)
+        }
+    }
+
+    /// Verifies the signature like `verify`, also returning the time spent in the
+    /// multiscalar multiplication. The timing covers only that cryptographic operation,
+    /// not the transcript or point decompression; it is zero if a point fails to decompress.
+    #[cfg(feature = "timed")]
+    pub fn verify_timed(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> (Result<(), ZkSchnorrError>, Duration) {
+        let c = compute_challenge(transcript, &pubkey, &self.R);
+        if self.is_degenerate() {
+            return (Err(ZkSchnorrError::DegenerateSignature), Duration::default());
+        }
+        match (decompress(&pubkey.g), decompress(&self.R), decompress(&pubkey.h)) {
+            (Some(g), Some(R), Some(h)) => {
+                let start = Instant::now();
+                let valid = verify_equation(&self.s, &R, &c, &g, &h);
+                let elapsed = start.elapsed();
+                if valid {
+                    (Ok(()), elapsed)
+                } else {
+                    (Err(ZkSchnorrError::InvalidSignature), elapsed)
+                }
+            }
+            _ => (Err(ZkSchnorrError::InvalidSignature), Duration::default()),
+        }
+    }
+
+    /// Verifies the signature like `verify`, using the already decompressed points of `pubkey`.
+    pub fn verify_prepared(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: &PreparedVerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        let c = compute_challenge(transcript, &pubkey.key(), &self.R);
+        let (g, h) = pubkey.points();
+        self.check_equation(&c, g, h)
+    }
+
+    fn check_equation(
+        &self,
+        c: &Scalar,
+        g: &RistrettoPoint,
+        h: &RistrettoPoint,
+    ) -> Result<(), ZkSchnorrError> {
+        if self.is_degenerate() {
+            return Err(ZkSchnorrError::DegenerateSignature);
+        }
+        match decompress(&self.R) {
+            Some(R) if verify_equation(&self.s, &R, c, g, h) => Ok(()),
+            _ => Err(ZkSchnorrError::InvalidSignature),
+        }
+    }
+
+    /// Verifies the signature against an aggregate of weighted verification keys.
+    ///
+    /// Each item `(key_i, w_i)` contributes `w_i·G_i` and `w_i·H_i` to the aggregate key
+    /// `(Σ w_i·G_i, Σ w_i·H_i)`, against which the signature is then verified as with
+    /// `verify`. The signature is valid if it was produced for that aggregate key, i.e.
+    /// by a signer knowing `x` such that `Σ w_i·H_i = x·Σ w_i·G_i`. Weights are used
+    /// as given: the caller is responsible for deriving them (e.g. from a transcript)
+    /// as required by the aggregation scheme. Fails if there are no items or a key
+    /// point is not a valid encoding.
+    pub fn verify_aggregate(
+        &self,
+        items: impl IntoIterator<Item = (VerificationKey, Scalar)>,
+        transcript: &mut Transcript,
+    ) -> Result<(), ZkSchnorrError> {
+        let mut items = items.into_iter().peekable();
+        if items.peek().is_none() {
+            return Err(ZkSchnorrError::InvalidSignature);
+        }
+        let mut g = RistrettoPoint::identity();
+        let mut h = RistrettoPoint::identity();
+        for (key, weight) in items {
+            let (key_g, key_h) = match (decompress(&key.g), decompress(&key.h)) {
+                (Some(key_g), Some(key_h)) => (key_g, key_h),
+                _ => return Err(ZkSchnorrError::InvalidSignature),
+            };
+            g += key_g * weight;
+            h += key_h * weight;
+        }
+        self.verify(
+            transcript,
+            VerificationKey::from_compressed(g.compress(), h.compress()),
+        )
+    }
+
+    /// Verifies the signature and, if it is valid, runs `cont` on the transcript
+    /// to continue a larger protocol. The transcript passed to `cont` is in the
+    /// same state the signer's transcript was in after `sign` returned.
+    pub fn verify_then<T>(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        cont: impl FnOnce(&mut Transcript) -> T,
+    ) -> Result<T, ZkSchnorrError> {
+        self.verify(transcript, pubkey)?;
+        Ok(cont(transcript))
+    }
+
+    /// Returns the nonce commitment `R`, which can be published ahead of the full signature.
+    pub fn commitment(&self) -> CompressedRistretto {
+        self.R
+    }
+
+    /// Splits the signature into its nonce commitment `R` and response scalar `s`.
+    pub fn into_parts(self) -> (CompressedRistretto, Scalar) {
+        (self.R, self.s)
+    }
+
+    /// Reassembles a signature from parts transmitted separately, checking that `s` is
+    /// a canonical scalar and `R` a canonical encoding of a Ristretto point.
+    pub fn from_parts(R: CompressedRistretto, s: Scalar) -> Result<Self, ZkSchnorrError> {
+        if !s.is_canonical() || decompress(&R).is_none() {
+            return Err(ZkSchnorrError::InvalidSignature);
+        }
+        Ok(Signature { s, R })
+    }
+
+    /// Verifies a signature given as its two halves, e.g. when `R` was published
+    /// earlier than `s`, without assembling a `Signature` first.
+    /// This is equivalent to `Signature::from_parts(*R, *s)?.verify(transcript, pubkey)`:
+    /// if `s` is not canonical or `R` not a valid point it fails with `InvalidSignature`
+    /// before touching the transcript.
+    pub fn verify_split(
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        R: &CompressedRistretto,
+        s: &Scalar,
+    ) -> Result<(), ZkSchnorrError> {
+        Self::from_parts(*R, *s)?.verify(transcript, pubkey)
+    }
+
+    /// Verifies the signature like `verify`, and additionally checks that it uses
+    /// the nonce commitment `R` that was published earlier.
+    pub fn verify_committed(
+        &self,
+        commitment: &CompressedRistretto,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        if self.R != *commitment {
+            return Err(ZkSchnorrError::InvalidSignature);
+        }
+        self.verify(transcript, pubkey)
+    }
+
+    /// Verifies that every signer signed the same transcript: `sigs[i]` is checked
+    /// against `keys[i]` over a copy of `transcript`, all in one batch (see `Verifier`).
+    /// Transcript should be in the same state as it was during the `sign` calls.
+    /// Fails with `NoSigners` if there are no signatures, with `InvalidBatch` if the
+    /// slices differ in length or any signature is invalid, or with `InvalidSignature`
+    /// if there is only one signer and its signature is invalid.
+    pub fn verify_multi(
+        transcript: &Transcript,
+        keys: &[VerificationKey],
+        sigs: &[Signature],
+    ) -> Result<(), ZkSchnorrError> {
+        if keys.len() != sigs.len() {
+            return Err(ZkSchnorrError::InvalidBatch);
+        }
+        Self::verify_signers(transcript, keys.iter().zip(sigs))
+    }
+
+    /// Verifies signatures by many signers over one shared transcript, like
+    /// `verify_multi`, but takes `(key, signature)` pairs and builds the transcript
+    /// by calling `transcript_fn` once. Each entry is checked against a clone of it,
+    /// since the challenge depends on the signer's key, and all checks are batched.
+    /// Fails like `verify_multi`: with `NoSigners` if `entries` is empty, with
+    /// `InvalidBatch` if any signature is invalid, or with `InvalidSignature` if
+    /// there is only one entry and it is invalid.
+    pub fn verify_shared_transcript<F>(
+        transcript_fn: F,
+        entries: &[(VerificationKey, Signature)],
+    ) -> Result<(), ZkSchnorrError>
+    where
+        F: FnOnce() -> Transcript,
+    {
+        let transcript = transcript_fn();
+        Self::verify_signers(&transcript, entries.iter().map(|(pubkey, sig)| (pubkey, sig)))
+    }
+
+    /// Batch-verifies each `(key, signature)` pair against a clone of `transcript`.
+    /// Shared by `verify_multi` and `verify_shared_transcript`.
+    fn verify_signers<'a>(
+        transcript: &Transcript,
+        signers: impl ExactSizeIterator<Item = (&'a VerificationKey, &'a Signature)>,
+    ) -> Result<(), ZkSchnorrError> {
+        if signers.len() == 0 {
+            return Err(ZkSchnorrError::NoSigners);
+        }
+        let mut verifier = Verifier::new();
+        for (pubkey, sig) in signers {
+            verifier.push(*sig, transcript.clone(), *pubkey)?;
+        }
+        verifier.verify()
     }
 
     /// Verifies the signature against a given verification key in a batch.
@@ -69,14 +432,35 @@ impl Signature {
         pubkey: VerificationKey,
         batch: &mut impl BatchVerification,
     ) {
-        // Make c = H(pubkey, R, m)
-        // The message has already been fed into the transcript
-        let c = {
-            transcript.zkschnorr_domain_sep();
-            transcript.append_point(b"G", &pubkey.g);
-            transcript.append_point(b"H", &pubkey.h);
-            transcript.append_point(b"R", &self.R);
-            transcript.challenge_scalar(b"challenge")
+        self.verify_batched_with_challenge(transcript, pubkey, None, batch);
+    }
+
+    /// Verifies the signature in a batch like `verify_batched`, but uses `challenge`
+    /// instead of deriving it from the transcript when it is provided.
+    /// The transcript is left untouched in that case.
+    ///
+    /// The cached challenge must have been computed with `challenge_for` for this exact
+    /// signature, key and transcript state. Supplying any other value breaks soundness:
+    /// the signature is then no longer bound to the message and key. Checking this is
+    /// the caller's responsibility.
+    pub fn verify_batched_with_challenge(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+        challenge: Option<&Scalar>,
+        batch: &mut impl BatchVerification,
+    ) {
+        let c = match challenge {
+            Some(c) => *c,
+            None => compute_challenge(transcript, &pubkey, &self.R),
+        };
+
+        // A degenerate signature is appended with a malformed `R`, so that
+        // every batch verifier rejects it.
+        let R = if self.is_degenerate() {
+            None
+        } else {
+            decompress(&self.R)
         };
 
         // Form the final linear combination:
@@ -86,11 +470,109 @@ impl Signature {
         batch.append(
             -self.s,
             iter::once(Scalar::one()).chain(iter::once(c)),
-            iter::once(pubkey.g.decompress())
-                .chain(iter::once(self.R.decompress()))
-                .chain(iter::once(pubkey.h.decompress())),
+            iter::once(decompress(&pubkey.g))
+                .chain(iter::once(R))
+                .chain(iter::once(decompress(&pubkey.h))),
         );
     }
+
+    /// Returns `true` if the signature is obviously malformed: `R` is the identity
+    /// or `s` is zero. Every verifier rejects such signatures, even where they satisfy
+    /// the verification equation (e.g. for a key with `G` and `H` at the identity):
+    /// `verify` and its variants fail with `DegenerateSignature`, a batch containing
+    /// one fails to verify, and `BatchVerifier::append_signature` and `Verifier::push`
+    /// refuse them upfront.
+    pub fn is_degenerate(&self) -> bool {
+        self.R == CompressedRistretto::identity() || self.s == Scalar::zero()
+    }
+
+    /// Returns `true` if the signature is degenerate (see `is_degenerate`) or its `R`
+    /// equals the `G` or `H` point of `pubkey`, i.e. the nonce was `1` or the signing key.
+    ///
+    /// This is a policy check for rejecting suspicious signatures defensively, e.g. when
+    /// ingesting untrusted data: the verification equation may still accept such
+    /// signatures, as they can be valid for the key. Ristretto encodings are canonical,
+    /// so comparing the compressed points is exact.
+    pub fn is_degenerate_for(&self, pubkey: &VerificationKey) -> bool {
+        self.is_degenerate() || self.R == pubkey.g || self.R == pubkey.h
+    }
+
+    /// Computes the challenge for this signature and key, to be cached and
+    /// passed to `verify_batched_with_challenge` when the same signature is
+    /// verified again. Transcript should be in the same state as it was during
+    /// the `sign` call that created the signature.
+    pub fn challenge_for(&self, transcript: &mut Transcript, pubkey: VerificationKey) -> Scalar {
+        compute_challenge(transcript, &pubkey, &self.R)
+    }
+
+    /// Returns true if both signatures carry the same nonce commitment `R`.
+    /// Two such signatures by the same key over different messages reveal the
+    /// private key (see `recover_key_from_nonce_reuse`).
+    pub fn shares_nonce(&self, other: &Signature) -> bool {
+        self.R == other.R
+    }
+}
+
+/// Recovers the private key from two signatures by the same key that reuse a nonce,
+/// given their challenges (see `Signature::challenge_for`).
+/// From s1 = r + c1*x and s2 = r + c2*x it follows that x = (s1 - s2) / (c1 - c2).
+/// Intended for auditing signature sets flagged by `Signature::shares_nonce`.
+/// Returns `None` if the challenges are equal, i.e. the same message was signed
+/// twice (or the same signature was passed twice), which reveals nothing.
+pub fn recover_key_from_nonce_reuse(
+    sig1: &Signature,
+    c1: &Scalar,
+    sig2: &Signature,
+    c2: &Scalar,
+) -> Option<Scalar> {
+    if c1 == c2 {
+        return None;
+    }
+    Some((sig1.s - sig2.s) * (c1 - c2).invert())
+}
+
+/// Makes c = H(pubkey, R, m), the challenge used by every signing and verification
+/// path of the transcript-based API.
+/// The message has already been fed into the transcript: the domain separator,
+/// `G`, `H` and `R` are appended and the `challenge` scalar is squeezed.
+pub fn compute_challenge(
+    transcript: &mut Transcript,
+    pubkey: &VerificationKey,
+    R: &CompressedRistretto,
+) -> Scalar {
+    transcript.zkschnorr_domain_sep();
+    transcript.append_point(G_LABEL, &pubkey.g);
+    transcript.append_point(H_LABEL, &pubkey.h);
+    transcript.append_point(R_LABEL, R);
+    transcript.challenge_scalar(CHALLENGE_LABEL)
+}
+
+/// Default source of external randomness for signing.
+#[cfg(feature = "getrandom")]
+pub(crate) fn default_rng() -> impl RngCore + CryptoRng {
+    rand_core::OsRng
+}
+
+/// Default source of external randomness for signing.
+#[cfg(not(feature = "getrandom"))]
+pub(crate) fn default_rng() -> impl RngCore + CryptoRng {
+    rand::thread_rng()
+}
+
+/// Evaluates the verification equation `s·G == R + c·H` for a given challenge `c`.
+///
+/// This is the core relation checked by `Signature::verify`, exposed without any
+/// transcript or RNG involvement so it can be audited and tested in isolation.
+pub fn verify_equation(
+    s: &Scalar,
+    R: &RistrettoPoint,
+    c: &Scalar,
+    g: &RistrettoPoint,
+    h: &RistrettoPoint,
+) -> bool {
+    // `0 == (-s * G) + (1 * R) + (c * H)`
+    RistrettoPoint::vartime_multiscalar_mul(&[-s, Scalar::one(), *c], &[*g, *R, *h])
+        .is_identity()
 }
 
 // Message-oriented API
@@ -124,8 +606,197 @@ impl Signature {
         self.verify(&mut Self::transcript_for_message(label, message), pubkey)
     }
 
-    fn transcript_for_message(label: &'static [u8], message: &[u8]) -> Transcript {
-        let mut t = Transcript::new(b"Elgamal.sign_message");
+    /// Verifies the signature over a message under each of the given labels in turn,
+    /// returning the first label that verifies. This allows accepting signatures made
+    /// under both an old and a new label scheme during a migration.
+    pub fn verify_message_with_labels(
+        &self,
+        labels: &[&'static [u8]],
+        message: &[u8],
+        pubkey: VerificationKey,
+    ) -> Result<&'static [u8], ZkSchnorrError> {
+        labels
+            .iter()
+            .find(|label| self.verify_message(label, message, pubkey).is_ok())
+            .copied()
+            .ok_or(ZkSchnorrError::InvalidSignature)
+    }
+
+    /// Signs a message like `sign_message`, but binds the verification key at the
+    /// message layer: the key bytes are appended to the transcript (labelled "pubkey")
+    /// before the message. The default API only commits the key while deriving the
+    /// challenge; committing it up front makes the whole message transcript specific
+    /// to one key, so the signature cannot be reinterpreted under a substituted key.
+    /// The transcript is labelled "ZkSchnorr.sign_message_key_committing"
+    /// (`KEY_COMMITTING_TRANSCRIPT_LABEL`), so such signatures only verify with
+    /// `verify_message_key_committing`.
+    pub fn sign_message_key_committing(
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut Self::key_committing_transcript_for_message(label, message, &pubkey),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_message_key_committing`.
+    pub fn verify_message_key_committing(
+        &self,
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(
+            &mut Self::key_committing_transcript_for_message(label, message, &pubkey),
+            pubkey,
+        )
+    }
+
+    /// Signs a Pedersen commitment (e.g. to a confidential amount) instead of a plaintext message.
+    /// Internally it creates a Transcript instance labelled "ZkSchnorr.sign_commitment",
+    /// and appends to it the compressed commitment point labelled with a user-provided `label`.
+    pub fn sign_commitment(
+        label: &'static [u8],
+        commitment: &CompressedRistretto,
+        pubkey: VerificationKey,
+        privkey: Scalar,
+    ) -> Signature {
+        Self::sign(
+            &mut Self::transcript_for_commitment(label, commitment),
+            pubkey,
+            privkey,
+        )
+    }
+
+    /// Verifies a signature created with `sign_commitment` over the same commitment.
+    pub fn verify_commitment(
+        &self,
+        label: &'static [u8],
+        commitment: &CompressedRistretto,
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        self.verify(&mut Self::transcript_for_commitment(label, commitment), pubkey)
+    }
+
+    /// Verifies the signature over a transcript described declaratively: a transcript
+    /// labelled `domain` to which each `(label, message)` pair of `appends` is appended
+    /// in order. This is equivalent to building the transcript with `Transcript::new`
+    /// and `append_message` calls before `verify`.
+    pub fn verify_reconstructed(
+        &self,
+        domain: &'static [u8],
+        appends: &[(&'static [u8], &[u8])],
+        pubkey: VerificationKey,
+    ) -> Result<(), ZkSchnorrError> {
+        let mut t = Transcript::new(domain);
+        for (label, message) in appends {
+            t.append_message(label, message);
+        }
+        self.verify(&mut t, pubkey)
+    }
+
+    /// Signs a message with a caller-supplied challenge derivation instead of the
+    /// Merlin transcript, e.g. to match an external Schnorr specification.
+    /// `challenge_fn` receives `G || H || R || message` (compressed points followed
+    /// by the raw message) and returns the challenge scalar `c`.
+    ///
+    /// The crate's domain separation does not apply here: binding the challenge to a
+    /// protocol and preventing cross-protocol reuse is the responsibility of `challenge_fn`.
+    /// The nonce is still derived from a transcript over the message and the private key,
+    /// mixed with external randomness as in `sign`.
+    pub fn sign_with_challenge_fn<F>(
+        message: &[u8],
+        pubkey: VerificationKey,
+        privkey: Scalar,
+        challenge_fn: F,
+    ) -> Signature
+    where
+        F: Fn(&[u8]) -> Scalar,
+    {
+        let mut transcript = Transcript::new(CHALLENGE_FN_TRANSCRIPT_LABEL);
+        transcript.append_message(b"message", message);
+        let mut rng = transcript
+            .build_rng()
+            .rekey_with_witness_bytes(b"x", &privkey.to_bytes())
+            .finalize(&mut default_rng());
+
+        let r = Scalar::random(&mut rng);
+        let R = (decompress(&pubkey.g).unwrap() * r).compress();
+        let c = challenge_fn(&Self::challenge_fn_input(&pubkey, &R, message));
+        Signature { s: r + c * privkey, R }
+    }
+
+    /// Verifies a signature created with `sign_with_challenge_fn` using the same `challenge_fn`.
+    pub fn verify_with_challenge_fn<F>(
+        &self,
+        message: &[u8],
+        pubkey: VerificationKey,
+        challenge_fn: F,
+    ) -> Result<(), ZkSchnorrError>
+    where
+        F: Fn(&[u8]) -> Scalar,
+    {
+        let c = challenge_fn(&Self::challenge_fn_input(&pubkey, &self.R, message));
+        match (decompress(&pubkey.g), decompress(&pubkey.h)) {
+            (Some(g), Some(h)) => self.check_equation(&c, &g, &h),
+            _ => Err(ZkSchnorrError::InvalidSignature),
+        }
+    }
+
+    fn challenge_fn_input(
+        pubkey: &VerificationKey,
+        R: &CompressedRistretto,
+        message: &[u8],
+    ) -> Vec<u8> {
+        let mut input = Vec::with_capacity(96 + message.len());
+        input.extend_from_slice(pubkey.g.as_bytes());
+        input.extend_from_slice(pubkey.h.as_bytes());
+        input.extend_from_slice(R.as_bytes());
+        input.extend_from_slice(message);
+        input
+    }
+
+    /// Signs the same message under each of the given keys in parallel.
+    /// Every signature draws an independent nonce from its worker thread's own RNG.
+    #[cfg(feature = "rayon")]
+    pub fn sign_many_parallel(
+        label: &'static [u8],
+        message: &[u8],
+        keys: &[(VerificationKey, Scalar)],
+    ) -> Vec<Signature> {
+        use rayon::prelude::*;
+
+        keys.par_iter()
+            .map(|(pubkey, privkey)| Self::sign_message(label, message, *pubkey, *privkey))
+            .collect()
+    }
+
+    pub(crate) fn transcript_for_message(label: &'static [u8], message: &[u8]) -> Transcript {
+        let mut t = Transcript::new(MESSAGE_TRANSCRIPT_LABEL);
+        t.append_message(label, message);
+        t
+    }
+
+    fn transcript_for_commitment(
+        label: &'static [u8],
+        commitment: &CompressedRistretto,
+    ) -> Transcript {
+        let mut t = Transcript::new(COMMITMENT_TRANSCRIPT_LABEL);
+        t.append_point(label, commitment);
+        t
+    }
+
+    fn key_committing_transcript_for_message(
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: &VerificationKey,
+    ) -> Transcript {
+        let mut t = Transcript::new(KEY_COMMITTING_TRANSCRIPT_LABEL);
+        t.append_message(b"pubkey", &pubkey.to_bytes());
         t.append_message(label, message);
         t
     }
@@ -133,13 +804,12 @@ impl Signature {
 
 impl fmt::Debug for Signature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Without hex crate we'd do this, but it outputs comma-separated numbers: [aa, 11, 5a, ...]
-        write!(
-            f,
-            "Signature({}{})",
-            hex::encode(&self.s.as_bytes()),
-            hex::encode(&self.R.as_bytes())
-        )
+        // Formatted as hex by hand, so that `Debug` does not depend on the `hex` feature.
+        f.write_str("Signature(")?;
+        for byte in self.s.as_bytes().iter().chain(self.R.as_bytes()) {
+            write!(f, "{:02x}", byte)?;
+        }
+        f.write_str(")")
     }
 }
 
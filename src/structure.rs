@@ -0,0 +1,97 @@
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::metrics::decompress;
+use super::signature::{default_rng, verify_equation};
+use super::transcript::{
+    TranscriptProtocol, CHALLENGE_LABEL, G_LABEL, H_LABEL, KEY_STRUCTURE_TRANSCRIPT_LABEL,
+};
+
+/// Standalone proof that a verification key `(G, H)` is well-formed:
+/// the prover knows `r` and `x` such that `G = r·B` and `H = x·G`.
+///
+/// Unlike a signature, the proof is not bound to any message, so it can be
+/// checked once when a key is registered.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct StructureProof {
+    /// Nonce commitment for `r`: `k_r·B`
+    pub R_g: CompressedRistretto,
+    /// Nonce commitment for `x`: `k_x·G`
+    pub R_h: CompressedRistretto,
+    /// Response for `r`: `k_r + c·r`
+    pub s_g: Scalar,
+    /// Response for `x`: `k_x + c·x`
+    pub s_h: Scalar,
+}
+
+impl VerificationKey {
+    /// Proves that the key `from_secret(privkey, r)` is well-formed.
+    pub fn prove_structure(privkey: &Scalar, r: &Scalar) -> StructureProof {
+        let key = Self::from_secret(privkey, r);
+        let g = Self::from_secret_decompressed(r);
+
+        let mut transcript = Transcript::new(KEY_STRUCTURE_TRANSCRIPT_LABEL);
+        let mut rng = transcript
+            .build_rng()
+            .rekey_with_witness_bytes(b"r", &r.to_bytes())
+            .rekey_with_witness_bytes(b"x", &privkey.to_bytes())
+            .finalize(&mut default_rng());
+        let k_g = Scalar::random(&mut rng);
+        let k_h = Scalar::random(&mut rng);
+        let R_g = (RISTRETTO_BASEPOINT_POINT * k_g).compress();
+        let R_h = (g * k_h).compress();
+
+        let c = structure_challenge(&mut transcript, &key, &R_g, &R_h);
+        StructureProof {
+            R_g,
+            R_h,
+            s_g: k_g + c * r,
+            s_h: k_h + c * privkey,
+        }
+    }
+
+    /// Verifies a proof that this key is well-formed, i.e. that `G` and `H`
+    /// share the discrete log relationship `H = x·G` for a known `x`.
+    pub fn verify_structure(&self, proof: &StructureProof) -> Result<(), ZkSchnorrError> {
+        let mut transcript = Transcript::new(KEY_STRUCTURE_TRANSCRIPT_LABEL);
+        let c = structure_challenge(&mut transcript, self, &proof.R_g, &proof.R_h);
+
+        let points = (
+            decompress(&self.g),
+            decompress(&self.h),
+            decompress(&proof.R_g),
+            decompress(&proof.R_h),
+        );
+        let valid = match points {
+            (Some(g), Some(h), Some(R_g), Some(R_h)) => {
+                // `s_g·B == R_g + c·G` and `s_h·G == R_h + c·H`
+                verify_equation(&proof.s_g, &R_g, &c, &RISTRETTO_BASEPOINT_POINT, &g)
+                    && verify_equation(&proof.s_h, &R_h, &c, &g, &h)
+            }
+            _ => false,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ZkSchnorrError::InvalidProof)
+        }
+    }
+}
+
+fn structure_challenge(
+    transcript: &mut Transcript,
+    key: &VerificationKey,
+    R_g: &CompressedRistretto,
+    R_h: &CompressedRistretto,
+) -> Scalar {
+    transcript.zkschnorr_domain_sep();
+    transcript.append_point(G_LABEL, &key.g);
+    transcript.append_point(H_LABEL, &key.h);
+    transcript.append_point(b"R_G", R_g);
+    transcript.append_point(b"R_H", R_h);
+    transcript.challenge_scalar(CHALLENGE_LABEL)
+}
@@ -2,6 +2,12 @@ use crate::{ signature::Signature, key::VerificationKey, errors::ZkSchnorrError,
 use curve25519_dalek::scalar::Scalar;
 use merlin::Transcript;
 
+/// Signing key `i` and its verification key, with a fixed base randomness.
+fn keypair(i: u64) -> (Scalar, VerificationKey) {
+    let privkey = Scalar::from(i);
+    (privkey, VerificationKey::from_secret(&privkey, &Scalar::from(10987u64)))
+}
+
 #[test]
 fn sign_and_verify_single() {
     let privkey = Scalar::from(1u64);
@@ -164,3 +170,2397 @@ fn sign_and_verify_batch() {
 
     assert_eq!(bad_batch.verify(), Err(ZkSchnorrError::InvalidBatch));*/
 }
+
+#[test]
+fn batch_is_valid_does_not_consume() {
+    let prv = Scalar::from(1u64);
+    let pubkey = VerificationKey::from_secret(&prv, &Scalar::random(&mut rand::thread_rng()));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, prv);
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, &mut batch);
+    assert!(batch.is_valid());
+    assert!(batch.is_valid());
+    assert!(batch.verify().is_ok());
+
+    let mut bad_batch = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"wrong message"), pubkey, &mut bad_batch);
+    assert!(!bad_batch.is_valid());
+    assert_eq!(bad_batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn verify_against_committed_nonce() {
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    let sig = Signature::sign(&mut Transcript::new(b"bid"), X, privkey);
+    let commitment = sig.commitment();
+    assert!(sig
+        .verify_committed(&commitment, &mut Transcript::new(b"bid"), X)
+        .is_ok());
+
+    // A different signature over the same bid uses a fresh nonce.
+    let other = Signature::sign(&mut Transcript::new(b"bid"), X, privkey);
+    assert!(other.verify(&mut Transcript::new(b"bid"), X).is_ok());
+    assert_eq!(
+        other.verify_committed(&commitment, &mut Transcript::new(b"bid"), X),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[cfg(feature = "timed")]
+#[test]
+fn verify_timed_invalid_point() {
+    use curve25519_dalek::ristretto::CompressedRistretto;
+    use std::time::Duration;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"timed"), pubkey, privkey);
+
+    // No multiplication is timed when `R` fails to decompress.
+    let bad = Signature {
+        s: sig.s,
+        R: CompressedRistretto([0xff; 32]),
+    };
+    assert_eq!(
+        bad.verify_timed(&mut Transcript::new(b"timed"), pubkey),
+        (Err(ZkSchnorrError::InvalidSignature), Duration::default())
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn sign_many_parallel() {
+    let keys: Vec<_> = (1..=16u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            let r = Scalar::random(&mut rand::thread_rng());
+            (VerificationKey::from_secret(&privkey, &r), privkey)
+        })
+        .collect();
+
+    let sigs = Signature::sign_many_parallel(b"label", b"message", &keys);
+    assert_eq!(sigs.len(), keys.len());
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter()) {
+        assert!(sig.verify_message(b"label", b"message", *pubkey).is_ok());
+    }
+}
+
+#[test]
+fn verify_then_continues_transcript() {
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    let mut prover = Transcript::new(b"layered protocol");
+    let sig = Signature::sign(&mut prover, X, privkey);
+    prover.append_message(b"next", b"proof");
+    let mut expected = [0u8; 32];
+    prover.challenge_bytes(b"next challenge", &mut expected);
+
+    let continued = sig
+        .verify_then(&mut Transcript::new(b"layered protocol"), X, |t| {
+            t.append_message(b"next", b"proof");
+            let mut buf = [0u8; 32];
+            t.challenge_bytes(b"next challenge", &mut buf);
+            buf
+        })
+        .unwrap();
+    assert_eq!(continued, expected);
+
+    let mut called = false;
+    assert_eq!(
+        sig.verify_then(&mut Transcript::new(b"other protocol"), X, |_| called = true),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert!(!called);
+}
+
+#[test]
+fn batch_with_repeated_key() {
+    let privkey = Scalar::from(7u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::random(&mut rand::thread_rng()));
+    let messages: Vec<&[u8]> = vec![b"message 1", b"message 2", b"message 3", b"message 4"];
+    let sigs: Vec<_> = messages
+        .iter()
+        .map(|m| Signature::sign_message(b"label", m, pubkey, privkey))
+        .collect();
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for (sig, m) in sigs.iter().zip(messages.iter()) {
+        let mut t = Transcript::new(b"Elgamal.sign_message");
+        t.append_message(b"label", m);
+        sig.verify_batched(&mut t, pubkey, &mut batch);
+    }
+    assert!(batch.verify().is_ok());
+
+    let mut bad_batch = BatchVerifier::new(rand::thread_rng());
+    for (sig, m) in sigs.iter().zip(messages.iter().rev()) {
+        let mut t = Transcript::new(b"Elgamal.sign_message");
+        t.append_message(b"label", m);
+        sig.verify_batched(&mut t, pubkey, &mut bad_batch);
+    }
+    assert_eq!(bad_batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn batch_with_huge_capacity() {
+    let privkey = Scalar::from(1u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+
+    for capacity in [usize::MAX, usize::MAX / 3 + 1] {
+        let mut batch = BatchVerifier::with_capacity(rand::thread_rng(), capacity);
+        sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, &mut batch);
+        assert!(batch.verify().is_ok());
+    }
+}
+
+#[test]
+fn encode_into_buffers() {
+    let privkey = Scalar::from(1u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+
+    let mut exact = [0u8; 64];
+    assert!(sig.encode_into(&mut exact).is_ok());
+    assert_eq!(exact, sig.to_bytes());
+    assert!(pubkey.encode_into(&mut exact).is_ok());
+    assert_eq!(exact.to_vec(), pubkey.to_bytes());
+
+    let mut oversize = [0xaau8; 80];
+    assert!(sig.encode_into(&mut oversize).is_ok());
+    assert_eq!(&oversize[..64], &sig.to_bytes()[..]);
+    assert_eq!(&oversize[64..], &[0xaa; 16][..]);
+    assert!(pubkey.encode_into(&mut oversize).is_ok());
+    assert_eq!(oversize[..64].to_vec(), pubkey.to_bytes());
+
+    let mut undersize = [0u8; 63];
+    assert_eq!(
+        sig.encode_into(&mut undersize),
+        Err(ZkSchnorrError::BufferTooSmall)
+    );
+    assert_eq!(
+        pubkey.encode_into(&mut undersize),
+        Err(ZkSchnorrError::BufferTooSmall)
+    );
+    assert_eq!(undersize, [0u8; 63]);
+}
+
+#[test]
+fn blinded_one_time_key() {
+    let privkey = Scalar::random(&mut rand::thread_rng());
+    let base = VerificationKey::from_secret(&privkey, &Scalar::random(&mut rand::thread_rng()));
+    let shared_secret = Scalar::random(&mut rand::thread_rng());
+
+    let one_time = base.blind(&shared_secret).unwrap();
+    let one_time_privkey = VerificationKey::blind_signing_key(&privkey, &shared_secret).unwrap();
+    assert_ne!(one_time.as_point().0, base.as_point().0);
+    assert_ne!(one_time.as_point().1, base.as_point().1);
+
+    let sig = Signature::sign_message(b"label", b"payment", one_time, one_time_privkey);
+    assert!(sig.verify_message(b"label", b"payment", one_time).is_ok());
+    assert!(sig.verify_message(b"label", b"payment", base).is_err());
+
+    // The base signing key alone can't sign for the one-time key.
+    let sig = Signature::sign_message(b"label", b"payment", one_time, privkey);
+    assert!(sig.verify_message(b"label", b"payment", one_time).is_err());
+
+    // Another secret gives an unrelated key.
+    let other = base.blind(&Scalar::random(&mut rand::thread_rng())).unwrap();
+    assert_ne!(other, one_time);
+
+    // A zero secret would map every key to the identity key.
+    assert_eq!(base.blind(&Scalar::zero()), Err(ZkSchnorrError::ZeroSharedSecret));
+    assert_eq!(
+        VerificationKey::blind_signing_key(&privkey, &Scalar::zero()),
+        Err(ZkSchnorrError::ZeroSharedSecret)
+    );
+}
+
+#[test]
+fn verify_equation_small_scalars() {
+    use crate::verify_equation;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as B;
+
+    // g = 2·B, x = 3, h = x·g = 6·B, nonce r = 5, R = r·g = 10·B, c = 7.
+    // s = r + c·x = 26, and s·g = 52·B = 10·B + 7·(6·B).
+    let g = B * Scalar::from(2u64);
+    let h = B * Scalar::from(6u64);
+    let R = B * Scalar::from(10u64);
+    let c = Scalar::from(7u64);
+
+    assert!(verify_equation(&Scalar::from(26u64), &R, &c, &g, &h));
+    assert!(!verify_equation(&Scalar::from(27u64), &R, &c, &g, &h));
+    assert!(!verify_equation(&Scalar::from(26u64), &R, &Scalar::from(8u64), &g, &h));
+    assert!(!verify_equation(&Scalar::from(26u64), &h, &c, &g, &R));
+
+    // Zero nonce and zero challenge: s = 0 holds only against the identity R.
+    let zero = Scalar::zero();
+    assert!(verify_equation(&zero, &(B * zero), &zero, &g, &h));
+    assert!(!verify_equation(&zero, &R, &zero, &g, &h));
+}
+
+#[test]
+fn key_committing_message_signature() {
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let Y = VerificationKey::from_secret(&Scalar::from(2u64), &Scalar::from(10987u64));
+
+    let sig = Signature::sign_message_key_committing(b"label", b"account", X, privkey);
+    assert!(sig
+        .verify_message_key_committing(b"label", b"account", X)
+        .is_ok());
+    assert!(sig
+        .verify_message_key_committing(b"label", b"account", Y)
+        .is_err());
+    assert!(sig
+        .verify_message_key_committing(b"label", b"other", X)
+        .is_err());
+
+    // Not interchangeable with the default message API.
+    assert!(sig.verify_message(b"label", b"account", X).is_err());
+    let plain = Signature::sign_message(b"label", b"account", X, privkey);
+    assert!(plain
+        .verify_message_key_committing(b"label", b"account", X)
+        .is_err());
+}
+
+#[test]
+fn batch_estimated_cost() {
+    let mut rng = rand::thread_rng();
+    let keys: Vec<_> = (2..=4u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            (VerificationKey::from_secret(&privkey, &Scalar::random(&mut rng)), privkey)
+        })
+        .collect();
+
+    let mut batch = BatchVerifier::new(rand::thread_rng()).with_combined_points();
+    assert_eq!(batch.estimated_cost(), 0);
+
+    // Distinct keys: every entry adds `g`, `R` and `h`.
+    for (i, (pubkey, privkey)) in keys.iter().enumerate() {
+        let sig = Signature::sign(&mut Transcript::new(b"example transcript"), *pubkey, *privkey);
+        sig.verify_batched(&mut Transcript::new(b"example transcript"), *pubkey, &mut batch);
+        assert_eq!(batch.estimated_cost(), 3 * (i + 1));
+    }
+
+    // Repeated key: only the new `R` is distinct.
+    let (pubkey, privkey) = keys[0];
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+    sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, &mut batch);
+    assert_eq!(batch.estimated_cost(), 10);
+    assert!(batch.verify().is_ok());
+}
+
+#[test]
+fn verify_message_with_migrated_labels() {
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign_message(b"new label", b"account", X, privkey);
+
+    assert_eq!(
+        sig.verify_message_with_labels(&[b"old label", b"new label"], b"account", X),
+        Ok(&b"new label"[..])
+    );
+    assert_eq!(
+        sig.verify_message_with_labels(&[b"old label", b"other label"], b"account", X),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        sig.verify_message_with_labels(&[], b"account", X),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn constant_time_bytes_comparison() {
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), X, privkey);
+
+    let bytes = sig.to_bytes();
+    assert!(bool::from(sig.ct_eq_bytes(&bytes)));
+
+    let mut tampered = bytes;
+    tampered[63] ^= 1;
+    assert!(!bool::from(sig.ct_eq_bytes(&tampered)));
+    assert!(!bool::from(sig.ct_eq_bytes(&bytes[..63])));
+    assert!(!bool::from(sig.ct_eq_bytes(&[])));
+}
+
+#[test]
+fn big_endian_round_trip() {
+    use crate::{scalar_from_bytes_be, scalar_to_bytes_be};
+
+    let privkey = Scalar::from(0x0102u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), X, privkey);
+
+    let be = sig.to_bytes_be();
+    let le = sig.to_bytes();
+    assert_eq!(be[..32], le[..32]);
+    let mut reversed = [0u8; 32];
+    reversed.copy_from_slice(&le[32..]);
+    reversed.reverse();
+    assert_eq!(be[32..], reversed);
+
+    let decoded = Signature::from_bytes_be(be).unwrap();
+    assert_eq!(decoded, sig);
+    assert!(decoded
+        .verify(&mut Transcript::new(b"example transcript"), X)
+        .is_ok());
+
+    let mut bad_r = be;
+    bad_r[..32].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Signature::from_bytes_be(bad_r),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+
+    let key_be = scalar_to_bytes_be(&privkey);
+    assert_eq!(key_be[30..], [0x01, 0x02]);
+    assert_eq!(scalar_from_bytes_be(key_be), Some(privkey));
+    assert_eq!(scalar_from_bytes_be([0xff; 32]), None);
+}
+
+#[test]
+fn verify_against_weighted_aggregate() {
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as B;
+
+    let mut rng = rand::thread_rng();
+    let privkey = Scalar::random(&mut rng);
+    let (r1, r2) = (Scalar::random(&mut rng), Scalar::random(&mut rng));
+    let key1 = VerificationKey::from_secret(&privkey, &r1);
+    let key2 = VerificationKey::from_secret(&privkey, &r2);
+    let (w1, w2) = (Scalar::from(3u64), Scalar::from(5u64));
+
+    // The aggregate key is (w1·r1 + w2·r2)·B with the same secret.
+    let agg_r = w1 * r1 + w2 * r2;
+    let agg = VerificationKey::from_secret(&privkey, &agg_r);
+    assert_eq!(agg.as_point().0, &(B * agg_r).compress());
+    let sig = Signature::sign(&mut Transcript::new(b"aggregate"), agg, privkey);
+
+    assert!(sig
+        .verify_aggregate(vec![(key1, w1), (key2, w2)], &mut Transcript::new(b"aggregate"))
+        .is_ok());
+    assert!(sig
+        .verify_aggregate(vec![(key1, w2), (key2, w1)], &mut Transcript::new(b"aggregate"))
+        .is_err());
+    assert!(sig
+        .verify_aggregate(vec![(key1, w1)], &mut Transcript::new(b"aggregate"))
+        .is_err());
+    assert!(sig
+        .verify_aggregate(vec![(key1, w1), (key2, w2)], &mut Transcript::new(b"other"))
+        .is_err());
+    assert_eq!(
+        sig.verify_aggregate(Vec::new(), &mut Transcript::new(b"aggregate")),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn batch_entry_limit() {
+    let privkey = Scalar::from(2u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+
+    let batch_of = |n: usize| {
+        let mut batch = BatchVerifier::new(rand::thread_rng());
+        for _ in 0..n {
+            sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, &mut batch);
+        }
+        batch
+    };
+
+    assert_eq!(batch_of(3).verify_with_limit(3), Ok(()));
+    assert_eq!(
+        batch_of(4).verify_with_limit(3),
+        Err(ZkSchnorrError::BatchTooLarge)
+    );
+    assert_eq!(batch_of(0).verify_with_limit(0), Ok(()));
+    assert_eq!(
+        batch_of(1).verify_with_limit(0),
+        Err(ZkSchnorrError::BatchTooLarge)
+    );
+}
+
+#[test]
+fn signing_key_bytes() {
+    use crate::{SigningKey, SigningKeyBytes};
+
+    let mut bytes = [0u8; 32];
+    bytes[0] = 42;
+    let privkey = SigningKey::from_scalar_bytes(bytes).unwrap();
+    assert_eq!(privkey, Scalar::from(42u64));
+    assert_eq!(privkey.to_scalar_bytes(), bytes);
+
+    let random = Scalar::random(&mut rand::thread_rng());
+    assert_eq!(SigningKey::from_scalar_bytes(random.to_scalar_bytes()), Ok(random));
+    assert_eq!(
+        SigningKey::from_scalar_bytes([0xff; 32]),
+        Err(ZkSchnorrError::InvalidScalar)
+    );
+}
+
+#[test]
+fn signature_parts_round_trip() {
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), X, privkey);
+
+    let (R, s) = sig.into_parts();
+    assert_eq!(R, sig.commitment());
+    let rebuilt = Signature::from_parts(R, s).unwrap();
+    assert_eq!(rebuilt, sig);
+    assert!(rebuilt
+        .verify(&mut Transcript::new(b"example transcript"), X)
+        .is_ok());
+
+    assert_eq!(
+        Signature::from_parts(CompressedRistretto([0xff; 32]), s),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::from_parts(R, Scalar::from_bits([0xff; 32])),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn sign_and_verify_commitment() {
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as B;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+
+    let privkey = Scalar::from(1u64);
+    let X = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    // Pedersen commitment to an amount `v` with blinding `b`: v·B + b·B_blinding.
+    let B_blinding = RistrettoPoint::from_uniform_bytes(&[7u8; 64]);
+    let blinding = Scalar::random(&mut rand::thread_rng());
+    let commit = |v: u64| (B * Scalar::from(v) + B_blinding * blinding).compress();
+
+    let sig = Signature::sign_commitment(b"amount", &commit(100), X, privkey);
+    assert!(sig.verify_commitment(b"amount", &commit(100), X).is_ok());
+    assert!(sig.verify_commitment(b"amount", &commit(101), X).is_err());
+    assert!(sig.verify_commitment(b"other", &commit(100), X).is_err());
+    assert!(sig
+        .verify_message(b"amount", commit(100).as_bytes(), X)
+        .is_err());
+}
+
+#[test]
+fn verifier_facade() {
+    use crate::Verifier;
+
+    let keys: Vec<_> = (2..5u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            (VerificationKey::from_secret(&privkey, &Scalar::from(10987u64)), privkey)
+        })
+        .collect();
+    let sigs: Vec<_> = keys
+        .iter()
+        .map(|(pubkey, privkey)| Signature::sign(&mut Transcript::new(b"facade"), *pubkey, *privkey))
+        .collect();
+
+    assert_eq!(Verifier::new().verify(), Ok(()));
+
+    // A single signature never needs an RNG.
+    let mut single = Verifier::new();
+    single.push(sigs[0], Transcript::new(b"facade"), keys[0].0).unwrap();
+    let no_rng = || -> rand::rngs::ThreadRng { panic!("single verification must not use an RNG") };
+    assert_eq!(single.verify_with_rng(no_rng), Ok(()));
+
+    let mut single = Verifier::new();
+    single.push(sigs[0], Transcript::new(b"facade"), keys[1].0).unwrap();
+    assert_eq!(single.verify(), Err(ZkSchnorrError::InvalidSignature));
+
+    // Several signatures are batched.
+    let mut many = Verifier::new();
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter()) {
+        many.push(*sig, Transcript::new(b"facade"), *pubkey).unwrap();
+    }
+    assert_eq!(many.len(), 3);
+    assert_eq!(many.verify(), Ok(()));
+
+    let mut many = Verifier::new();
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter().rev()) {
+        many.push(*sig, Transcript::new(b"facade"), *pubkey).unwrap();
+    }
+    assert_eq!(
+        many.verify_with_rng(rand::thread_rng),
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+}
+
+#[test]
+fn key_structure_proof() {
+    let mut rng = rand::thread_rng();
+    let privkey = Scalar::random(&mut rng);
+    let r = Scalar::random(&mut rng);
+    let key = VerificationKey::from_secret(&privkey, &r);
+
+    let proof = VerificationKey::prove_structure(&privkey, &r);
+    assert_eq!(key.verify_structure(&proof), Ok(()));
+
+    // The proof is bound to the key.
+    let other = VerificationKey::from_secret(&Scalar::random(&mut rng), &r);
+    assert_eq!(other.verify_structure(&proof), Err(ZkSchnorrError::InvalidProof));
+
+    // A proof made with the wrong secret does not verify for the key.
+    let forged = VerificationKey::prove_structure(&(privkey + Scalar::one()), &r);
+    assert_eq!(key.verify_structure(&forged), Err(ZkSchnorrError::InvalidProof));
+
+    // Tampered responses are rejected.
+    let mut tampered = proof;
+    tampered.s_h += Scalar::one();
+    assert_eq!(key.verify_structure(&tampered), Err(ZkSchnorrError::InvalidProof));
+    let mut tampered = proof;
+    tampered.s_g += Scalar::one();
+    assert_eq!(key.verify_structure(&tampered), Err(ZkSchnorrError::InvalidProof));
+    let mut swapped = proof;
+    swapped.R_g = proof.R_h;
+    swapped.R_h = proof.R_g;
+    assert_eq!(key.verify_structure(&swapped), Err(ZkSchnorrError::InvalidProof));
+}
+
+#[test]
+fn batch_append_signature() {
+    let keys: Vec<_> = (2..5u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            (VerificationKey::from_secret(&privkey, &Scalar::from(10987u64)), privkey)
+        })
+        .collect();
+    let sigs: Vec<Signature> = keys
+        .iter()
+        .map(|(pubkey, privkey)| Signature::sign(&mut Transcript::new(b"borrowed"), *pubkey, *privkey))
+        .collect();
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter()) {
+        batch.append_signature(&mut Transcript::new(b"borrowed"), *pubkey, sig).unwrap();
+    }
+    assert!(batch.verify().is_ok());
+
+    let mut bad_batch = BatchVerifier::new(rand::thread_rng());
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter().rev()) {
+        bad_batch.append_signature(&mut Transcript::new(b"borrowed"), *pubkey, sig).unwrap();
+    }
+    assert_eq!(bad_batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn batch_with_short_weights() {
+    let keys: Vec<_> = (2..6u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            (VerificationKey::from_secret(&privkey, &Scalar::from(10987u64)), privkey)
+        })
+        .collect();
+    let sigs: Vec<Signature> = keys
+        .iter()
+        .map(|(pubkey, privkey)| Signature::sign(&mut Transcript::new(b"short"), *pubkey, *privkey))
+        .collect();
+
+    let mut batch = BatchVerifier::new(rand::thread_rng()).with_short_weights();
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter()) {
+        batch.append_signature(&mut Transcript::new(b"short"), *pubkey, sig).unwrap();
+    }
+    assert!(batch.verify().is_ok());
+
+    let mut bad_batch = BatchVerifier::new(rand::thread_rng()).with_short_weights();
+    for (sig, (pubkey, _)) in sigs.iter().zip(keys.iter()) {
+        bad_batch.append_signature(&mut Transcript::new(b"short"), *pubkey, sig).unwrap();
+    }
+    bad_batch.append_signature(&mut Transcript::new(b"wrong"), keys[0].0, &sigs[0]).unwrap();
+    assert_eq!(bad_batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn prepared_verification_key() {
+    use crate::PreparedVerificationKey;
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let privkey = Scalar::random(&mut rand::thread_rng());
+    let r = Scalar::random(&mut rand::thread_rng());
+    let prepared = VerificationKey::from_secret_prepared(&privkey, &r);
+    let key = VerificationKey::from_secret(&privkey, &r);
+    assert_eq!(prepared.key(), key);
+    assert_eq!(PreparedVerificationKey::prepare(key), Ok(prepared));
+
+    let sig = Signature::sign_prepared(&mut Transcript::new(b"prepared"), &prepared, privkey);
+    assert!(sig.verify(&mut Transcript::new(b"prepared"), key).is_ok());
+    assert!(sig
+        .verify_prepared(&mut Transcript::new(b"prepared"), &prepared)
+        .is_ok());
+    assert!(sig
+        .verify_prepared(&mut Transcript::new(b"other"), &prepared)
+        .is_err());
+
+    let invalid = VerificationKey::new(CompressedRistretto([0xff; 32]), *key.as_point().1);
+    assert_eq!(
+        PreparedVerificationKey::prepare(invalid),
+        Err(ZkSchnorrError::InvalidPoint)
+    );
+}
+
+#[test]
+fn degenerate_signatures_are_rejected() {
+    use crate::{BatchVerifier, SingleVerifier, Verifier, VerifyFailure};
+    use curve25519_dalek::ristretto::CompressedRistretto;
+    use curve25519_dalek::traits::Identity;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"degenerate"), pubkey, privkey);
+    assert!(!sig.is_degenerate());
+
+    let zero = Signature {
+        s: Scalar::zero(),
+        R: CompressedRistretto::identity(),
+    };
+    let zero_s = Signature {
+        s: Scalar::zero(),
+        R: sig.R,
+    };
+    let identity_r = Signature {
+        s: sig.s,
+        R: CompressedRistretto::identity(),
+    };
+
+    for bad in [zero, zero_s, identity_r].iter() {
+        assert!(bad.is_degenerate());
+
+        let mut batch = BatchVerifier::new(rand::thread_rng());
+        batch
+            .append_signature(&mut Transcript::new(b"degenerate"), pubkey, &sig)
+            .unwrap();
+        assert_eq!(
+            batch.append_signature(&mut Transcript::new(b"degenerate"), pubkey, bad),
+            Err(ZkSchnorrError::DegenerateSignature)
+        );
+        // The rejected entry is not part of the batch.
+        assert!(batch.verify().is_ok());
+
+        let mut verifier = Verifier::new();
+        assert_eq!(
+            verifier.push(*bad, Transcript::new(b"degenerate"), pubkey),
+            Err(ZkSchnorrError::DegenerateSignature)
+        );
+        assert!(verifier.is_empty());
+
+        // Every other entry point rejects it as well.
+        assert_eq!(
+            bad.verify(&mut Transcript::new(b"degenerate"), pubkey),
+            Err(ZkSchnorrError::DegenerateSignature)
+        );
+        assert_eq!(
+            bad.verify_detailed(&mut Transcript::new(b"degenerate"), pubkey),
+            Err(VerifyFailure::Degenerate)
+        );
+        assert!(SingleVerifier::verify(|v| {
+            bad.verify_batched(&mut Transcript::new(b"degenerate"), pubkey, v)
+        })
+        .is_err());
+        let mut batch = BatchVerifier::new(rand::thread_rng());
+        sig.verify_batched(&mut Transcript::new(b"degenerate"), pubkey, &mut batch);
+        bad.verify_batched(&mut Transcript::new(b"degenerate"), pubkey, &mut batch);
+        assert_eq!(batch.validate_keys(), vec![1]);
+        assert_eq!(batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+    }
+}
+
+#[test]
+fn short_signature_round_trip() {
+    use crate::ShortSignature;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign_short(&mut Transcript::new(b"short"), pubkey, privkey);
+    assert!(sig.verify_short(&mut Transcript::new(b"short"), pubkey).is_ok());
+    assert!(sig.verify_short(&mut Transcript::new(b"other"), pubkey).is_err());
+    let other = VerificationKey::from_secret(&Scalar::from(4u64), &Scalar::from(10987u64));
+    assert!(sig.verify_short(&mut Transcript::new(b"short"), other).is_err());
+
+    let bytes = sig.to_bytes();
+    assert_eq!(bytes.len(), 49);
+    let decoded = ShortSignature::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, sig);
+    assert!(decoded.verify_short(&mut Transcript::new(b"short"), pubkey).is_ok());
+
+    // The two wire formats cannot be mistaken for each other.
+    let full = Signature::sign(&mut Transcript::new(b"short"), pubkey, privkey);
+    assert!(ShortSignature::from_bytes(&full.to_bytes()).is_err());
+    assert!(Signature::from_bytes(&bytes[..]).is_err());
+    let mut wrong_tag = bytes;
+    wrong_tag[0] ^= 1;
+    assert!(ShortSignature::from_bytes(&wrong_tag).is_err());
+}
+
+#[test]
+fn verify_batched_with_cached_challenge() {
+    use crate::BatchVerifier;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"cached"), pubkey, privkey);
+    let c = sig.challenge_for(&mut Transcript::new(b"cached"), pubkey);
+
+    // The transcript is not consulted when the challenge is supplied.
+    for _ in 0..3 {
+        let mut batch = BatchVerifier::new(rand::thread_rng());
+        sig.verify_batched_with_challenge(&mut Transcript::new(b"unused"), pubkey, Some(&c), &mut batch);
+        assert!(batch.verify().is_ok());
+    }
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched_with_challenge(&mut Transcript::new(b"cached"), pubkey, None, &mut batch);
+    assert!(batch.verify().is_ok());
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    let wrong = c + Scalar::one();
+    sig.verify_batched_with_challenge(&mut Transcript::new(b"cached"), pubkey, Some(&wrong), &mut batch);
+    assert!(batch.verify().is_err());
+}
+
+#[test]
+fn transcript_labels_do_not_drift() {
+    use crate::{
+        CACHE_DIGEST_LABEL, CHALLENGE_FN_TRANSCRIPT_LABEL, CHALLENGE_LABEL,
+        COMMITMENTS_TRANSCRIPT_LABEL, COMMITMENT_TRANSCRIPT_LABEL, COUNTERSIGN_TRANSCRIPT_LABEL,
+        COUNTER_TRANSCRIPT_LABEL, DOMAIN_SEP_LABEL, FINGERPRINT_TRANSCRIPT_LABEL, G_LABEL,
+        HASH_TO_SCALAR_TRANSCRIPT_LABEL, H_LABEL, KEY_AGGREGATION_LABEL,
+        KEY_COMMITTING_TRANSCRIPT_LABEL, KEY_ROTATION_TRANSCRIPT_LABEL,
+        KEY_STRUCTURE_TRANSCRIPT_LABEL, MESSAGE_TRANSCRIPT_LABEL, PREHASH_TRANSCRIPT_LABEL,
+        PROTOCOL_TRANSCRIPT_LABEL, READER_TRANSCRIPT_LABEL, REQUEST_TRANSCRIPT_LABEL, R_LABEL,
+        SHORT_SIGNING_DOMAIN, SIGNABLE_TRANSCRIPT_LABEL, SIGNING_DOMAIN,
+    };
+
+    assert_eq!(DOMAIN_SEP_LABEL, b"dom-sep");
+    assert_eq!(SIGNING_DOMAIN, b"ElGamalSign v1");
+    assert_eq!(SHORT_SIGNING_DOMAIN, b"ElGamalSign short v1");
+    assert_eq!(G_LABEL, b"G");
+    assert_eq!(H_LABEL, b"H");
+    assert_eq!(R_LABEL, b"R");
+    assert_eq!(CHALLENGE_LABEL, b"challenge");
+    assert_eq!(MESSAGE_TRANSCRIPT_LABEL, b"Elgamal.sign_message");
+    assert_eq!(SIGNABLE_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_signable");
+    assert_eq!(
+        KEY_COMMITTING_TRANSCRIPT_LABEL,
+        b"ZkSchnorr.sign_message_key_committing"
+    );
+    assert_eq!(PROTOCOL_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_in_protocol");
+    assert_eq!(PREHASH_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_prehashed");
+    assert_eq!(READER_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_message_reader");
+    assert_eq!(COUNTER_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_with_counter");
+    assert_eq!(COMMITMENTS_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_commitments");
+    assert_eq!(COMMITMENT_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_commitment");
+    assert_eq!(REQUEST_TRANSCRIPT_LABEL, b"ZkSchnorr.sign_request");
+    assert_eq!(KEY_AGGREGATION_LABEL, b"ZkSchnorr.aggregate_keys");
+    assert_eq!(COUNTERSIGN_TRANSCRIPT_LABEL, b"ZkSchnorr.countersign");
+    assert_eq!(KEY_ROTATION_TRANSCRIPT_LABEL, b"ZkSchnorr.key_rotation");
+    assert_eq!(KEY_STRUCTURE_TRANSCRIPT_LABEL, b"ZkSchnorr.key_structure");
+    assert_eq!(FINGERPRINT_TRANSCRIPT_LABEL, b"ZkSchnorr.fingerprint");
+    assert_eq!(HASH_TO_SCALAR_TRANSCRIPT_LABEL, b"ZkSchnorr.hash_to_scalar");
+    assert_eq!(CHALLENGE_FN_TRANSCRIPT_LABEL, b"ZkSchnorr.challenge_fn");
+    assert_eq!(CACHE_DIGEST_LABEL, b"ZkSchnorr.cache");
+
+    // A verifier built from the constants accepts signatures from `sign_message`.
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign_message(b"msg", b"hello", pubkey, privkey);
+
+    let mut t = Transcript::new(MESSAGE_TRANSCRIPT_LABEL);
+    t.append_message(b"msg", b"hello");
+    t.append_message(DOMAIN_SEP_LABEL, SIGNING_DOMAIN);
+    t.append_message(G_LABEL, pubkey.as_point().0.as_bytes());
+    t.append_message(H_LABEL, pubkey.as_point().1.as_bytes());
+    t.append_message(R_LABEL, sig.R.as_bytes());
+    let mut buf = [0u8; 64];
+    t.challenge_bytes(CHALLENGE_LABEL, &mut buf);
+    let c = Scalar::from_bytes_mod_order_wide(&buf);
+
+    let (g, h) = (
+        pubkey.as_point().0.decompress().unwrap(),
+        pubkey.as_point().1.decompress().unwrap(),
+    );
+    assert!(crate::verify_equation(&sig.s, &sig.R.decompress().unwrap(), &c, &g, &h));
+}
+
+#[test]
+fn verification_key_points_valid() {
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let key = VerificationKey::from_secret(&Scalar::from(3u64), &Scalar::from(10987u64));
+    assert!(key.points_valid());
+    assert!(VerificationKey::default().points_valid());
+
+    let (g, h) = key.into_point();
+    let invalid = CompressedRistretto([0xff; 32]);
+    assert!(!VerificationKey::new(invalid, h).points_valid());
+    assert!(!VerificationKey::new(g, invalid).points_valid());
+    // A non-canonical encoding of a field element is not a valid point.
+    let mut non_canonical = [0xffu8; 32];
+    non_canonical[0] = 0xec;
+    non_canonical[31] = 0x7f;
+    assert!(!VerificationKey::new(g, CompressedRistretto(non_canonical)).points_valid());
+}
+
+#[test]
+fn sign_with_custom_challenge_fn() {
+    use sha2::Sha512;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sha512 = |input: &[u8]| Scalar::hash_from_bytes::<Sha512>(input);
+
+    let sig = Signature::sign_with_challenge_fn(b"hello", pubkey, privkey, sha512);
+    assert!(sig.verify_with_challenge_fn(b"hello", pubkey, sha512).is_ok());
+    assert!(sig.verify_with_challenge_fn(b"bye", pubkey, sha512).is_err());
+
+    // The challenge is computed over `G || H || R || message`.
+    let mut input = pubkey.to_bytes();
+    input.extend_from_slice(sig.R.as_bytes());
+    input.extend_from_slice(b"hello");
+    let c = sha512(&input);
+    let g = pubkey.as_point().0.decompress().unwrap();
+    let h = pubkey.as_point().1.decompress().unwrap();
+    assert!(crate::verify_equation(&sig.s, &sig.R.decompress().unwrap(), &c, &g, &h));
+
+    // A different challenge function does not accept the signature.
+    let other = |input: &[u8]| sha512(input) + Scalar::one();
+    assert!(sig.verify_with_challenge_fn(b"hello", pubkey, other).is_err());
+}
+
+#[test]
+fn batch_seal_rounds() {
+    use crate::BatchVerifier;
+
+    let keys: Vec<_> = (2..5).map(keypair).collect();
+
+    let mut batch = BatchVerifier::with_capacity(rand::thread_rng(), 3);
+    for round in 0..3 {
+        for (privkey, pubkey) in keys.iter() {
+            let sig = Signature::sign(&mut Transcript::new(b"round"), *pubkey, *privkey);
+            batch
+                .append_signature(&mut Transcript::new(b"round"), *pubkey, &sig)
+                .unwrap();
+        }
+        if round == 1 {
+            let sig = Signature::sign(&mut Transcript::new(b"other"), keys[0].1, keys[0].0);
+            batch
+                .append_signature(&mut Transcript::new(b"round"), keys[0].1, &sig)
+                .unwrap();
+            assert_eq!(batch.seal(), Err(ZkSchnorrError::InvalidBatch));
+        } else {
+            assert_eq!(batch.seal(), Ok(()));
+        }
+        // Each round starts empty.
+        assert_eq!(batch.estimated_cost(), 0);
+    }
+    assert_eq!(
+        batch.sealed_results(),
+        &[Ok(()), Err(ZkSchnorrError::InvalidBatch), Ok(())]
+    );
+    assert!(batch.verify().is_ok());
+}
+
+#[test]
+fn verify_stream_short_circuits() {
+    use crate::verify_stream;
+    use std::cell::Cell;
+
+    let keys: Vec<_> = (2..9u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            (VerificationKey::from_secret(&privkey, &Scalar::from(10987u64)), privkey)
+        })
+        .collect();
+    let items: Vec<_> = keys
+        .iter()
+        .map(|(pubkey, privkey)| {
+            let sig = Signature::sign(&mut Transcript::new(b"stream"), *pubkey, *privkey);
+            (Transcript::new(b"stream"), *pubkey, sig)
+        })
+        .collect();
+
+    for batch_size in 0..9 {
+        assert_eq!(verify_stream(items.clone(), batch_size), Ok(()));
+    }
+    assert_eq!(verify_stream(Vec::new(), 3), Ok(()));
+
+    // The fourth item fails: the second sub-batch is rejected
+    // and no item after it is read.
+    let mut bad = items.clone();
+    bad[3].0 = Transcript::new(b"other");
+    let read = Cell::new(0);
+    let stream = bad.into_iter().inspect(|_| read.set(read.get() + 1));
+    assert_eq!(verify_stream(stream, 2), Err(ZkSchnorrError::InvalidBatch));
+    assert_eq!(read.get(), 4);
+}
+
+#[test]
+fn verification_key_fingerprint() {
+    use std::collections::HashSet;
+
+    let r = Scalar::from(10987u64);
+    let key = VerificationKey::from_secret(&Scalar::from(3u64), &r);
+    assert_eq!(key.fingerprint(), key.fingerprint());
+    assert_eq!(
+        key.fingerprint(),
+        VerificationKey::from_bytes(&key.to_bytes()).unwrap().fingerprint()
+    );
+
+    let fingerprints: HashSet<_> = (1..100u64)
+        .map(|x| VerificationKey::from_secret(&Scalar::from(x), &r).fingerprint())
+        .chain((1..100u64).map(|r| VerificationKey::from_secret(&Scalar::from(3u64), &Scalar::from(r)).fingerprint()))
+        .collect();
+    // `x = 3, r = 10987` is not in the second range, so all 198 keys are distinct.
+    assert_eq!(fingerprints.len(), 198);
+}
+
+#[test]
+fn batch_result_is_independent_of_order() {
+    use crate::BatchVerifier;
+    use rand::seq::SliceRandom;
+
+    let entries: Vec<_> = (2..10u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            // Two keys share `G`, and one signature is repeated.
+            let r = Scalar::from(10987u64 + i % 2);
+            let pubkey = VerificationKey::from_secret(&privkey, &r);
+            let sig = Signature::sign(&mut Transcript::new(b"order"), pubkey, privkey);
+            (pubkey, sig)
+        })
+        .collect();
+    let mut entries = entries;
+    entries.push(entries[0]);
+
+    let verify = |entries: &[(VerificationKey, Signature)]| {
+        let mut batch = BatchVerifier::new(rand::thread_rng());
+        for (pubkey, sig) in entries {
+            batch
+                .append_signature(&mut Transcript::new(b"order"), *pubkey, sig)
+                .unwrap();
+        }
+        batch.verify()
+    };
+
+    let mut invalid = entries.clone();
+    invalid[3].1 = Signature::sign(&mut Transcript::new(b"other"), invalid[3].0, Scalar::from(5u64));
+
+    for (entries, expected) in [
+        (entries, Ok(())),
+        (invalid, Err(ZkSchnorrError::InvalidBatch)),
+    ]
+    .iter()
+    {
+        let expected = expected.clone();
+        assert_eq!(verify(entries), expected);
+        let mut reversed = entries.clone();
+        reversed.reverse();
+        assert_eq!(verify(&reversed), expected);
+        for _ in 0..5 {
+            let mut shuffled = entries.clone();
+            shuffled.shuffle(&mut rand::thread_rng());
+            assert_eq!(verify(&shuffled), expected);
+        }
+    }
+}
+
+#[test]
+fn batch_validate_keys() {
+    use crate::BatchVerifier;
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let entries: Vec<_> = (2..7u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+            let sig = Signature::sign(&mut Transcript::new(b"keys"), pubkey, privkey);
+            (pubkey, sig)
+        })
+        .collect();
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for (i, (pubkey, sig)) in entries.iter().enumerate() {
+        let pubkey = if i == 2 {
+            VerificationKey::new(*pubkey.as_point().0, CompressedRistretto([0xff; 32]))
+        } else {
+            *pubkey
+        };
+        batch
+            .append_signature(&mut Transcript::new(b"keys"), pubkey, sig)
+            .unwrap();
+    }
+    assert_eq!(batch.validate_keys(), vec![2]);
+    assert_eq!(batch.seal(), Err(ZkSchnorrError::InvalidBatch));
+
+    // A wrong signature with valid keys is not reported.
+    for (i, (pubkey, sig)) in entries.iter().enumerate() {
+        let label: &'static [u8] = if i == 2 { b"other" } else { b"keys" };
+        batch
+            .append_signature(&mut Transcript::new(label), *pubkey, sig)
+            .unwrap();
+    }
+    assert!(batch.validate_keys().is_empty());
+    assert_eq!(batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn verify_detailed_failure_modes() {
+    use crate::VerifyFailure;
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"detailed"), pubkey, privkey);
+    let verify = |sig: &Signature, label: &'static [u8], pubkey: VerificationKey| {
+        sig.verify_detailed(&mut Transcript::new(label), pubkey)
+    };
+
+    assert_eq!(verify(&sig, b"detailed", pubkey), Ok(()));
+    assert_eq!(
+        verify(&sig, b"other", pubkey),
+        Err(VerifyFailure::EquationMismatch)
+    );
+    let other = VerificationKey::from_secret(&Scalar::from(4u64), &Scalar::from(10987u64));
+    assert_eq!(
+        verify(&sig, b"detailed", other),
+        Err(VerifyFailure::EquationMismatch)
+    );
+
+    let malformed = CompressedRistretto([0xff; 32]);
+    let bad_key = VerificationKey::new(*pubkey.as_point().0, malformed);
+    assert_eq!(
+        verify(&sig, b"detailed", bad_key),
+        Err(VerifyFailure::MalformedKey)
+    );
+    let bad_r = Signature { s: sig.s, R: malformed };
+    assert_eq!(
+        verify(&bad_r, b"detailed", pubkey),
+        Err(VerifyFailure::MalformedR)
+    );
+    let non_canonical = Signature {
+        s: Scalar::from_bits([0xff; 32]),
+        R: sig.R,
+    };
+    assert_eq!(
+        verify(&non_canonical, b"detailed", pubkey),
+        Err(VerifyFailure::NonCanonicalScalar)
+    );
+
+    // The coarse error is unchanged.
+    assert_eq!(
+        ZkSchnorrError::from(VerifyFailure::MalformedR),
+        ZkSchnorrError::InvalidSignature
+    );
+    assert_eq!(
+        non_canonical.verify(&mut Transcript::new(b"detailed"), pubkey),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn message_builder_round_trip() {
+    use crate::MessageBuilder;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let message = |amount: u64| {
+        MessageBuilder::new()
+            .append(b"recipient", [7u8; 32])
+            .append(b"amount", amount.to_le_bytes())
+            .append(b"memo", "rent")
+    };
+
+    let sig = Signature::sign_message_builder(&message(100), pubkey, privkey);
+    assert!(sig.verify_message_builder(&message(100), pubkey).is_ok());
+    assert!(sig.verify_message_builder(&message(101), pubkey).is_err());
+    assert_eq!(message(100).fields().len(), 3);
+
+    // Field order matters.
+    let reordered = MessageBuilder::new()
+        .append(b"amount", 100u64.to_le_bytes())
+        .append(b"recipient", [7u8; 32])
+        .append(b"memo", "rent");
+    assert!(sig.verify_message_builder(&reordered, pubkey).is_err());
+
+    // A single field matches the plain message API.
+    let single = MessageBuilder::new().append(b"msg", b"hello");
+    let sig = Signature::sign_message(b"msg", b"hello", pubkey, privkey);
+    assert!(sig.verify_message_builder(&single, pubkey).is_ok());
+}
+
+#[test]
+fn signature_wire_size() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"size"), pubkey, privkey);
+    assert_eq!(sig.to_bytes().len(), 64);
+    assert_eq!(sig.to_bytes_be().len(), 64);
+    assert_eq!(
+        core::mem::size_of::<curve25519_dalek::ristretto::CompressedRistretto>()
+            + core::mem::size_of::<Scalar>(),
+        64
+    );
+}
+
+#[test]
+fn key_rotation_chain() {
+    use crate::{verify_chain, RotationCert};
+
+    let secrets: Vec<_> = (2..6u64).map(|i| (Scalar::from(i), Scalar::from(100 + i))).collect();
+    let keys: Vec<_> = secrets
+        .iter()
+        .map(|(privkey, r)| VerificationKey::from_secret(privkey, r))
+        .collect();
+    let certs: Vec<_> = secrets
+        .windows(2)
+        .zip(keys[1..].iter())
+        .map(|(pair, new_key)| RotationCert::rotate(&pair[0].0, &pair[0].1, *new_key))
+        .collect();
+
+    assert_eq!(verify_chain(keys[0], &certs, keys[3]), Ok(()));
+    assert_eq!(verify_chain(keys[0], &certs[..2], keys[2]), Ok(()));
+    assert_eq!(verify_chain(keys[1], &certs[1..], keys[3]), Ok(()));
+    assert_eq!(verify_chain(keys[0], &[], keys[0]), Ok(()));
+
+    // Wrong leaf or root.
+    assert_eq!(
+        verify_chain(keys[0], &certs, keys[2]),
+        Err(ZkSchnorrError::InvalidKeyChain)
+    );
+    assert_eq!(
+        verify_chain(keys[1], &certs, keys[3]),
+        Err(ZkSchnorrError::InvalidKeyChain)
+    );
+
+    // Broken link: the middle certificate points elsewhere.
+    let mut broken = certs.clone();
+    broken[1].new_key = keys[0];
+    assert_eq!(
+        verify_chain(keys[0], &broken, keys[3]),
+        Err(ZkSchnorrError::InvalidKeyChain)
+    );
+
+    // Skipping a link.
+    assert_eq!(
+        verify_chain(keys[0], &[certs[0], certs[2]], keys[3]),
+        Err(ZkSchnorrError::InvalidKeyChain)
+    );
+}
+
+#[test]
+fn batch_soundness() {
+    use crate::batch_soundness_bits;
+
+    assert_eq!(batch_soundness_bits(10, 128), 128.0);
+    assert_eq!(batch_soundness_bits(1_000_000, 128), 128.0);
+    assert_eq!(batch_soundness_bits(10, 64), 64.0);
+    assert_eq!(batch_soundness_bits(10, 256), 252.0);
+    assert_eq!(batch_soundness_bits(10, 512), 252.0);
+    assert_eq!(batch_soundness_bits(10, 0), 0.0);
+    assert_eq!(batch_soundness_bits(0, 128), f64::INFINITY);
+    // Probability that an invalid batch passes with 128-bit weights.
+    assert_eq!(2f64.powf(-batch_soundness_bits(10, 128)), 2.938735877055719e-39);
+}
+
+#[test]
+fn verify_reconstructed_transcript() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    let mut t = Transcript::new(b"transfer");
+    t.append_message(b"from", b"alice");
+    t.append_message(b"to", b"bob");
+    t.append_message(b"amount", &10u64.to_le_bytes());
+    let sig = Signature::sign(&mut t, pubkey, privkey);
+
+    let amount = 10u64.to_le_bytes();
+    let appends: [(&'static [u8], &[u8]); 3] =
+        [(b"from", b"alice"), (b"to", b"bob"), (b"amount", &amount)];
+    assert!(sig
+        .verify_reconstructed(b"transcript", &appends, pubkey)
+        .is_err());
+    assert!(sig.verify_reconstructed(b"transfer", &appends, pubkey).is_ok());
+    assert!(sig
+        .verify_reconstructed(b"transfer", &appends[..2], pubkey)
+        .is_err());
+    let swapped = [appends[1], appends[0], appends[2]];
+    assert!(sig.verify_reconstructed(b"transfer", &swapped, pubkey).is_err());
+
+    // Messages built through the message API use the message transcript label.
+    let sig = Signature::sign_message(b"msg", b"hello", pubkey, privkey);
+    assert!(sig
+        .verify_reconstructed(crate::MESSAGE_TRANSCRIPT_LABEL, &[(b"msg", b"hello")], pubkey)
+        .is_ok());
+}
+
+#[test]
+fn signing_session_commit_and_finalize() {
+    use crate::SigningSession;
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    let session = SigningSession::commit(&mut Transcript::new(b"session"), pubkey, &privkey);
+    let commitment = session.commitment();
+    assert_eq!(session.pubkey(), pubkey);
+    let public = session.public();
+    assert_eq!(public.commitment(), commitment);
+    assert_eq!(public.pubkey(), pubkey);
+
+    let sig = session.finalize(&privkey);
+    assert_eq!(sig.R, commitment);
+    assert!(sig
+        .verify_committed(&commitment, &mut Transcript::new(b"session"), pubkey)
+        .is_ok());
+    assert!(sig.verify(&mut Transcript::new(b"other"), pubkey).is_err());
+}
+
+#[test]
+fn effective_point_round_trip() {
+    let privkey = Scalar::from(3u64);
+    let r = Scalar::from(10987u64);
+    let key = VerificationKey::from_secret(&privkey, &r);
+
+    let h = key.effective_point();
+    assert_eq!(h, *key.as_point().1);
+    let g = *key.as_point().0;
+    assert_eq!(
+        h.decompress().unwrap(),
+        privkey * g.decompress().unwrap()
+    );
+
+    let restored = VerificationKey::from_effective(g, h);
+    assert_eq!(restored, key);
+    let sig = Signature::sign(&mut Transcript::new(b"effective"), key, privkey);
+    assert!(sig.verify(&mut Transcript::new(b"effective"), restored).is_ok());
+}
+
+#[test]
+fn batch_append_checked_revocation() {
+    use crate::BatchVerifier;
+    use std::collections::HashSet;
+
+    let entries: Vec<_> = (2..7u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+            let sig = Signature::sign(&mut Transcript::new(b"revoked"), pubkey, privkey);
+            (pubkey, sig)
+        })
+        .collect();
+
+    let mut revoked = HashSet::new();
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for (pubkey, sig) in entries.iter() {
+        batch
+            .append_checked(&mut Transcript::new(b"revoked"), *pubkey, sig, &revoked)
+            .unwrap();
+    }
+    assert!(batch.seal().is_ok());
+
+    revoked.insert(entries[1].0.to_bytes_array());
+    revoked.insert(entries[3].0.to_bytes_array());
+    for (pubkey, sig) in entries.iter() {
+        batch
+            .append_checked(&mut Transcript::new(b"revoked"), *pubkey, sig, &revoked)
+            .unwrap();
+    }
+    assert!(!batch.is_valid());
+    assert_eq!(batch.verify(), Err(ZkSchnorrError::RevokedKeys(vec![1, 3])));
+}
+
+#[test]
+fn verify_multi_same_message() {
+    let secrets: Vec<_> = (2..6u64).map(Scalar::from).collect();
+    let keys: Vec<_> = secrets
+        .iter()
+        .map(|privkey| VerificationKey::from_secret(privkey, &Scalar::from(10987u64)))
+        .collect();
+    let mut transcript = Transcript::new(b"multi");
+    transcript.append_message(b"proposal", b"upgrade to v2");
+    let sigs: Vec<_> = keys
+        .iter()
+        .zip(secrets.iter())
+        .map(|(pubkey, privkey)| Signature::sign(&mut transcript.clone(), *pubkey, *privkey))
+        .collect();
+
+    assert_eq!(Signature::verify_multi(&transcript, &keys, &sigs), Ok(()));
+
+    // One signer signed something else.
+    let mut bad = sigs.clone();
+    let mut other = Transcript::new(b"multi");
+    other.append_message(b"proposal", b"upgrade to v3");
+    bad[2] = Signature::sign(&mut other, keys[2], secrets[2]);
+    assert_eq!(
+        Signature::verify_multi(&transcript, &keys, &bad),
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+
+    assert_eq!(
+        Signature::verify_multi(&transcript, &keys[..3], &sigs),
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+
+    // Nobody signed.
+    assert_eq!(
+        Signature::verify_multi(&transcript, &[], &[]),
+        Err(ZkSchnorrError::NoSigners)
+    );
+}
+
+#[test]
+fn sign_and_verify_share_compute_challenge() {
+    use crate::{compute_challenge, verify_equation, BatchVerifier};
+
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"challenge"), pubkey, privkey);
+
+    // The signer's `s = r + c·x` uses the same challenge the verifier recomputes.
+    let c = compute_challenge(&mut Transcript::new(b"challenge"), &pubkey, &sig.R);
+    let (g, h) = (
+        pubkey.as_point().0.decompress().unwrap(),
+        pubkey.as_point().1.decompress().unwrap(),
+    );
+    assert!(verify_equation(&sig.s, &sig.R.decompress().unwrap(), &c, &g, &h));
+    assert_eq!(sig.challenge_for(&mut Transcript::new(b"challenge"), pubkey), c);
+
+    // Single and batched verification agree with it.
+    assert!(sig.verify(&mut Transcript::new(b"challenge"), pubkey).is_ok());
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"challenge"), pubkey, &mut batch);
+    sig.verify_batched_with_challenge(&mut Transcript::new(b"unused"), pubkey, Some(&c), &mut batch);
+    assert!(batch.verify().is_ok());
+
+    let other = compute_challenge(&mut Transcript::new(b"other"), &pubkey, &sig.R);
+    assert_ne!(other, c);
+}
+
+#[test]
+fn sign_with_shared_prefix() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let mut prefix = Transcript::new(b"payments");
+    prefix.append_message(b"sender", b"alice");
+    prefix.append_message(b"protocol", b"v1");
+    let scratch = |amount: u64| {
+        let mut t = Transcript::new(b"payments");
+        t.append_message(b"sender", b"alice");
+        t.append_message(b"protocol", b"v1");
+        t.append_message(b"amount", &amount.to_le_bytes());
+        t
+    };
+
+    for amount in 0..3u64 {
+        let suffix = |t: &mut Transcript| t.append_message(b"amount", &amount.to_le_bytes());
+        let sig = Signature::sign_with_prefix(&prefix, suffix, pubkey, privkey);
+        assert!(sig.verify(&mut scratch(amount), pubkey).is_ok());
+        assert!(sig.verify(&mut scratch(amount + 1), pubkey).is_err());
+
+        let sig = Signature::sign(&mut scratch(amount), pubkey, privkey);
+        assert!(sig.verify_with_prefix(&prefix, suffix, pubkey).is_ok());
+    }
+}
+
+#[test]
+fn recover_key_from_reused_nonce() {
+    use crate::{compute_challenge, recover_key_from_nonce_reuse};
+
+    let privkey = Scalar::from(0xdead_beefu64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let g = pubkey.g.decompress().unwrap();
+
+    // Sign two messages with the same nonce.
+    let nonce = Scalar::from(42u64);
+    let R = (g * nonce).compress();
+    let sign = |msg: &[u8]| {
+        let mut t = Transcript::new(b"example transcript");
+        t.append_message(b"message", msg);
+        let c = compute_challenge(&mut t, &pubkey, &R);
+        (Signature { s: nonce + c * privkey, R }, c)
+    };
+    let (sig1, c1) = sign(b"first");
+    let (sig2, c2) = sign(b"second");
+
+    let mut t = Transcript::new(b"example transcript");
+    t.append_message(b"message", b"first");
+    assert!(sig1.verify(&mut t, pubkey).is_ok());
+    assert!(sig1.shares_nonce(&sig2));
+    assert_eq!(recover_key_from_nonce_reuse(&sig1, &c1, &sig2, &c2), Some(privkey));
+    assert_eq!(recover_key_from_nonce_reuse(&sig1, &c1, &sig1, &c1), None);
+
+    let fresh = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+    assert!(!sig1.shares_nonce(&fresh));
+}
+
+#[test]
+fn verify_batch_lazy_matches_buffered() {
+    use crate::verify_batch_lazy;
+
+    let entries: Vec<_> = (0..8u64)
+        .map(|i| {
+            let privkey = Scalar::from(i + 1);
+            let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64 + i));
+            let msg = i.to_le_bytes();
+            let mut t = Transcript::new(b"lazy batch");
+            t.append_message(b"msg", &msg);
+            let sig = Signature::sign(&mut t.clone(), pubkey, privkey);
+            (t, pubkey, sig)
+        })
+        .collect();
+    assert!(verify_batch_lazy(&entries, rand::thread_rng()).is_ok());
+    assert!(verify_batch_lazy(&entries[..0], rand::thread_rng()).is_ok());
+
+    // A signature verified against another entry's transcript.
+    let mut bad = entries.clone();
+    bad[3].2 = entries[4].2;
+    assert_eq!(
+        verify_batch_lazy(&bad, rand::thread_rng()),
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+
+    // A key that does not decompress.
+    let mut bad = entries.clone();
+    let invalid = curve25519_dalek::ristretto::CompressedRistretto([0xff; 32]);
+    bad[5].1 = VerificationKey::from_compressed(invalid, entries[5].1.h);
+    assert_eq!(
+        verify_batch_lazy(&bad, rand::thread_rng()),
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+
+    let mut bad = entries;
+    bad[0].2.s = Scalar::zero();
+    assert_eq!(
+        verify_batch_lazy(&bad, rand::thread_rng()),
+        Err(ZkSchnorrError::DegenerateSignature)
+    );
+}
+
+#[test]
+fn sign_in_protocol_rejects_other_protocols() {
+    use crate::ProtocolId;
+
+    const PAYMENTS: ProtocolId = ProtocolId::new(b"payments v1");
+    const VOTING: ProtocolId = ProtocolId::new(b"voting v1");
+
+    let privkey = Scalar::from(7u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign_in_protocol(PAYMENTS, b"msg", b"approve 42", pubkey, privkey);
+
+    assert!(sig.verify_in_protocol(PAYMENTS, b"msg", b"approve 42", pubkey).is_ok());
+    assert!(sig.verify_in_protocol(VOTING, b"msg", b"approve 42", pubkey).is_err());
+    assert!(sig.verify_message(b"msg", b"approve 42", pubkey).is_err());
+
+    let plain = Signature::sign_message(b"msg", b"approve 42", pubkey, privkey);
+    assert!(plain.verify_in_protocol(PAYMENTS, b"msg", b"approve 42", pubkey).is_err());
+}
+
+#[test]
+fn batch_signature_with_external_relation() {
+    use crate::BatchVerification;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as B;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+
+    // A Pedersen commitment `C = v·B + b·B2` opened by an external proof system.
+    let b2 = RistrettoPoint::hash_from_bytes::<sha2::Sha512>(b"blinding base");
+    let (v, b) = (Scalar::from(42u64), Scalar::from(1234u64));
+    let commitment = v * B + b * b2;
+    let opening = |v: Scalar| ([v, b, -Scalar::one()], [B, b2, commitment]);
+
+    let privkey = Scalar::from(5u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, &mut batch);
+    let (scalars, points) = opening(v);
+    batch.append_relation(scalars, points);
+    batch.append_relation(vec![], vec![]);
+    assert!(batch.verify().is_ok());
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, &mut batch);
+    let (scalars, points) = opening(v + Scalar::one());
+    batch.append_relation(scalars, points);
+    assert_eq!(batch.verify(), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn verified_token_carries_key() {
+    use crate::Verified;
+
+    // Downstream code that must only run for a verified signer.
+    fn credit(balances: &mut Vec<(VerificationKey, u64)>, signer: Verified, amount: u64) {
+        balances.push((signer.into_inner(), amount));
+    }
+
+    let privkeys: Vec<_> = (1..4u64).map(Scalar::from).collect();
+    let pubkeys: Vec<_> = privkeys
+        .iter()
+        .map(|x| VerificationKey::from_secret(x, &Scalar::from(10987u64)))
+        .collect();
+    let transcript = Transcript::new(b"example transcript");
+    let sig = Signature::sign(&mut transcript.clone(), pubkeys[1], privkeys[1]);
+
+    let signer = sig.verify_any(&transcript, &pubkeys).unwrap();
+    assert_eq!(signer.key(), &pubkeys[1]);
+    assert_eq!(
+        sig.verify_any(&transcript, &[pubkeys[0], pubkeys[2]]),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert!(sig.verify_token(&mut transcript.clone(), pubkeys[0]).is_err());
+
+    let mut balances = Vec::new();
+    credit(&mut balances, signer, 10);
+    assert_eq!(balances, vec![(pubkeys[1], 10)]);
+
+    let sig = Signature::sign_message(b"msg", b"hello", pubkeys[0], privkeys[0]);
+    let signer = sig.verify_message_token(b"msg", b"hello", pubkeys[0]).unwrap();
+    assert_eq!(signer.into_inner(), pubkeys[0]);
+}
+
+#[test]
+fn batch_verify_bitmap() {
+    use std::collections::HashSet;
+
+    let pubkeys: Vec<_> = (1..=10u64)
+        .map(|i| VerificationKey::from_secret(&Scalar::from(i), &Scalar::from(10987u64 + i)))
+        .collect();
+    let sigs: Vec<_> = (1..=10u64)
+        .map(|i| {
+            let mut t = Transcript::new(b"bitmap");
+            Signature::sign(&mut t, pubkeys[i as usize - 1], Scalar::from(i))
+        })
+        .collect();
+
+    let mut revoked = HashSet::new();
+    revoked.insert(pubkeys[8].to_bytes_array());
+    let malformed = VerificationKey::from_compressed(
+        curve25519_dalek::ristretto::CompressedRistretto([0xff; 32]),
+        pubkeys[5].h,
+    );
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for (i, sig) in sigs.iter().enumerate() {
+        let label: &'static [u8] = if i == 2 || i == 7 { b"other" } else { b"bitmap" };
+        let pubkey = if i == 5 { malformed } else { pubkeys[i] };
+        batch
+            .append_checked(&mut Transcript::new(label), pubkey, sig, &revoked)
+            .unwrap();
+    }
+    assert_eq!(
+        batch.verify_bitmap(),
+        Ok(vec![true, true, false, true, true, false, true, false, false, true])
+    );
+
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for (pubkey, sig) in pubkeys.iter().zip(&sigs) {
+        sig.verify_batched(&mut Transcript::new(b"bitmap"), *pubkey, &mut batch);
+    }
+    assert_eq!(batch.verify_bitmap(), Ok(vec![true; 10]));
+    assert_eq!(BatchVerifier::new(rand::thread_rng()).verify_bitmap(), Ok(vec![]));
+}
+
+#[test]
+fn hash_to_scalar_vector() {
+    use crate::hash_to_scalar;
+
+    let scalar = hash_to_scalar(b"test domain", b"input");
+    assert_eq!(
+        hex::encode(scalar.as_bytes()),
+        "91225ddfd94f954137cb61e1d7b28e2e5f5e85d2fb43af633dad8bae7c60380d"
+    );
+    assert_eq!(scalar, hash_to_scalar(b"test domain", b"input"));
+    assert_ne!(scalar, hash_to_scalar(b"other domain", b"input"));
+    assert_ne!(scalar, hash_to_scalar(b"test domain", b"inpu"));
+    // Domain and input are length-prefixed, so shifting bytes between them changes the result.
+    assert_ne!(scalar, hash_to_scalar(b"test domai", b"ninput"));
+}
+
+#[test]
+fn batch_limits_reject_appends() {
+    let privkey = Scalar::from(4u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"limits"), pubkey, privkey);
+
+    // Entry limit.
+    let mut batch = BatchVerifier::with_limits(rand::thread_rng(), 2, usize::MAX);
+    for _ in 0..2 {
+        batch
+            .append_signature(&mut Transcript::new(b"limits"), pubkey, &sig)
+            .unwrap();
+    }
+    let usage = batch.memory_usage();
+    assert_eq!(
+        batch.append_signature(&mut Transcript::new(b"limits"), pubkey, &sig),
+        Err(ZkSchnorrError::BatchTooLarge)
+    );
+    assert_eq!(batch.memory_usage(), usage);
+    assert!(batch.verify().is_ok());
+
+    // Byte limit, fitting exactly two signatures.
+    let mut probe = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut probe);
+    let per_signature = probe.memory_usage();
+    assert!(per_signature > 0);
+
+    let mut batch = BatchVerifier::with_limits(rand::thread_rng(), 10, 2 * per_signature);
+    for _ in 0..2 {
+        batch
+            .append_signature(&mut Transcript::new(b"limits"), pubkey, &sig)
+            .unwrap();
+    }
+    assert_eq!(
+        batch.append_signature(&mut Transcript::new(b"limits"), pubkey, &sig),
+        Err(ZkSchnorrError::BatchTooLarge)
+    );
+    assert!(batch.memory_usage() <= 2 * per_signature);
+
+    // Appending past the limit through the trait drops the entry and fails verification.
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut batch);
+    assert!(batch.memory_usage() <= 2 * per_signature);
+    assert!(!batch.is_valid());
+    assert_eq!(batch.seal(), Err(ZkSchnorrError::BatchTooLarge));
+
+    // Sealing clears the batch and the flag.
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut batch);
+    assert_eq!(batch.verify(), Ok(()));
+}
+
+#[test]
+fn batch_limits_bound_allocation() {
+    use crate::BatchVerification;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+    use std::collections::HashSet;
+
+    let privkey = Scalar::from(4u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"limits"), pubkey, privkey);
+
+    // An oversized relation is dropped before the batch grows for it.
+    let max_bytes = 4096;
+    let mut batch = BatchVerifier::with_limits(rand::thread_rng(), 10, max_bytes);
+    batch.append(
+        Scalar::one(),
+        std::iter::repeat_n(Scalar::one(), 1_000_000),
+        std::iter::repeat_n(Some(RISTRETTO_BASEPOINT_POINT), 1_000_001),
+    );
+    assert_eq!(batch.memory_usage(), 0);
+    assert_eq!(batch.seal(), Err(ZkSchnorrError::BatchTooLarge));
+
+    // Spare capacity counts towards the limit.
+    assert!(BatchVerifier::with_capacity(rand::thread_rng(), 4).memory_usage() > 0);
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut batch);
+    assert!(batch.memory_usage() <= max_bytes);
+
+    // Revoked entries of a round that failed with `BatchTooLarge` do not leak into the next.
+    let revoked: HashSet<[u8; 64]> = std::iter::once(pubkey.to_bytes_array()).collect();
+    let mut batch = BatchVerifier::with_limits(rand::thread_rng(), 1, usize::MAX);
+    batch
+        .append_checked(&mut Transcript::new(b"limits"), pubkey, &sig, &revoked)
+        .unwrap();
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut batch);
+    assert_eq!(batch.seal(), Err(ZkSchnorrError::BatchTooLarge));
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut batch);
+    assert_eq!(batch.seal(), Ok(()));
+}
+
+#[test]
+fn batch_limits_apply_to_bitmap_and_merged_parts() {
+    let privkey = Scalar::from(4u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"limits"), pubkey, privkey);
+    let limited = |n: usize| {
+        let mut batch = BatchVerifier::with_limits(rand::thread_rng(), 1, usize::MAX);
+        for _ in 0..n {
+            sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut batch);
+        }
+        batch
+    };
+
+    // A dropped entry would shift the bitmap, so it is reported as an error.
+    assert_eq!(limited(1).verify_bitmap(), Ok(vec![true]));
+    assert_eq!(limited(2).verify_bitmap(), Err(ZkSchnorrError::BatchTooLarge));
+
+    // Merged parts keep the sum of their limits.
+    let mut merged = BatchVerifier::from_parts(rand::thread_rng(), vec![limited(1), limited(1)]);
+    assert_eq!(
+        merged.append_signature(&mut Transcript::new(b"limits"), pubkey, &sig),
+        Err(ZkSchnorrError::BatchTooLarge)
+    );
+    assert!(merged.verify().is_ok());
+
+    // An unlimited part leaves the merged verifier unlimited.
+    let mut unlimited = BatchVerifier::new(rand::thread_rng());
+    sig.verify_batched(&mut Transcript::new(b"limits"), pubkey, &mut unlimited);
+    let mut merged = BatchVerifier::from_parts(rand::thread_rng(), vec![limited(1), unlimited]);
+    merged
+        .append_signature(&mut Transcript::new(b"limits"), pubkey, &sig)
+        .unwrap();
+    assert!(merged.verify().is_ok());
+}
+
+#[test]
+fn countersigned_notarization() {
+    use crate::{countersign, verify_countersigned};
+
+    let privkey = Scalar::from(21u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let ts_privkey = Scalar::from(22u64);
+    let ts_key = VerificationKey::from_secret(&ts_privkey, &Scalar::from(5u64));
+
+    let original = Signature::sign_message(b"doc", b"contract v1", pubkey, privkey);
+    let other = Signature::sign_message(b"doc", b"contract v2", pubkey, privkey);
+    let counter = countersign(&original, pubkey, ts_key, ts_privkey);
+
+    assert!(original.verify_message(b"doc", b"contract v1", pubkey).is_ok());
+    assert!(verify_countersigned(&original, &counter, pubkey, ts_key).is_ok());
+    // Countersignature over a different original.
+    assert!(verify_countersigned(&other, &counter, pubkey, ts_key).is_err());
+    // Wrong signer or authority key.
+    assert!(verify_countersigned(&original, &counter, ts_key, ts_key).is_err());
+    assert!(verify_countersigned(&original, &counter, pubkey, pubkey).is_err());
+    // The original signature is not a countersignature of itself.
+    assert!(verify_countersigned(&original, &original, pubkey, pubkey).is_err());
+}
+
+#[test]
+fn signature_from_shares_verifies_against_summed_key() {
+    use crate::compute_challenge;
+
+    // Three participants share the base randomness `r`, so every share has the same `g`.
+    let r = Scalar::from(10987u64);
+    let share_keys: Vec<_> = (1..=3u64).map(|i| Scalar::from(100 + i)).collect();
+    let shares: Vec<_> = share_keys
+        .iter()
+        .map(|x| VerificationKey::from_secret(x, &r))
+        .collect();
+    let group_key = VerificationKey::from_shares(&shares).unwrap();
+    let n_inv = Scalar::from(3u64).invert();
+
+    // Each participant contributes a nonce commitment and a partial response.
+    let g = group_key.g.decompress().unwrap();
+    let nonces: Vec<_> = (1..=3u64).map(|i| Scalar::from(7 * i)).collect();
+    let R = (g * nonces.iter().sum::<Scalar>()).compress();
+    let mut transcript = Transcript::new(b"dkg");
+    let c = compute_challenge(&mut transcript.clone(), &group_key, &R);
+    let s = nonces
+        .iter()
+        .zip(&share_keys)
+        .map(|(k, x)| k + c * x * n_inv)
+        .sum();
+
+    let sig = Signature { s, R };
+    assert!(sig.verify(&mut transcript, group_key).is_ok());
+    assert!(sig.verify(&mut Transcript::new(b"dkg"), shares[0]).is_err());
+
+    // The group key is the ordinary key of the averaged share keys.
+    let averaged = share_keys.iter().sum::<Scalar>() * n_inv;
+    let single = Signature::sign(&mut Transcript::new(b"dkg"), group_key, averaged);
+    assert!(single.verify(&mut Transcript::new(b"dkg"), group_key).is_ok());
+
+    assert_eq!(
+        VerificationKey::from_shares(&[]),
+        Err(ZkSchnorrError::NoKeyShares)
+    );
+    let invalid = VerificationKey::from_compressed(
+        curve25519_dalek::ristretto::CompressedRistretto([0xff; 32]),
+        shares[0].h,
+    );
+    assert_eq!(
+        VerificationKey::from_shares(&[shares[0], invalid]),
+        Err(ZkSchnorrError::InvalidPoint)
+    );
+}
+
+#[test]
+fn sign_and_verify_streamed_message() {
+    use crate::MESSAGE_CHUNK_LEN;
+    use std::io::{self, Read};
+
+    /// Returns the data in reads of varying length.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.step = self.step % 7919 + 1;
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+    }
+
+    let privkey = Scalar::from(17u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let message: Vec<u8> = (0..3 * MESSAGE_CHUNK_LEN + 123).map(|i| i as u8).collect();
+
+    let sig = Signature::sign_message_reader(b"data", &message[..], pubkey, privkey).unwrap();
+    let trickle = Trickle { data: &message, step: 0 };
+    assert!(sig.verify_message_reader(b"data", trickle, pubkey).is_ok());
+    assert!(sig.verify_message_reader(b"data", &message[1..], pubkey).is_err());
+    assert!(sig.verify_message(b"data", &message, pubkey).is_err());
+    assert_eq!(
+        sig.verify_message_reader(b"data", Failing, pubkey),
+        Err(ZkSchnorrError::MessageRead(io::ErrorKind::ConnectionReset))
+    );
+
+    // Messages of exactly one chunk and empty messages are distinct.
+    let one_chunk = vec![0u8; MESSAGE_CHUNK_LEN];
+    let sig = Signature::sign_message_reader(b"data", &one_chunk[..], pubkey, privkey).unwrap();
+    assert!(sig.verify_message_reader(b"data", &one_chunk[..], pubkey).is_ok());
+    assert!(sig.verify_message_reader(b"data", io::empty(), pubkey).is_err());
+}
+
+#[test]
+fn public_constants_match_signing() {
+    use crate::{BASEPOINT, GROUP_ORDER_BYTES};
+    use curve25519_dalek::constants::BASEPOINT_ORDER;
+
+    assert_eq!(GROUP_ORDER_BYTES, BASEPOINT_ORDER.to_bytes());
+    assert_eq!(Scalar::from_bits(GROUP_ORDER_BYTES).reduce(), Scalar::zero());
+
+    let r = Scalar::from(10987u64);
+    assert_eq!(VerificationKey::from_secret_decompressed(&r), r * BASEPOINT);
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::one());
+    assert_eq!(pubkey.g, BASEPOINT.compress());
+    assert_eq!(pubkey.h, (privkey * BASEPOINT).compress());
+
+    let sig = Signature::sign(&mut Transcript::new(b"constants"), pubkey, privkey);
+    assert!(sig.verify(&mut Transcript::new(b"constants"), pubkey).is_ok());
+}
+
+#[test]
+fn detect_standard_base() {
+    let privkey = Scalar::from(8u64);
+    let standard = VerificationKey::from_secret(&privkey, &Scalar::one());
+    let r = Scalar::random(&mut rand::thread_rng());
+    let random_base = VerificationKey::from_secret(&privkey, &r);
+    assert!(standard.uses_standard_base());
+    assert!(!random_base.uses_standard_base());
+
+    // Both signing paths produce signatures that verify.
+    for pubkey in [standard, random_base].iter() {
+        let sig = Signature::sign(&mut Transcript::new(b"base"), *pubkey, privkey);
+        assert!(sig.verify(&mut Transcript::new(b"base"), *pubkey).is_ok());
+    }
+}
+
+#[test]
+fn abstain_records_are_distinct() {
+    let privkey = Scalar::from(12u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let ballot = || {
+        let mut t = Transcript::new(b"vote");
+        t.append_message(b"proposal", b"42");
+        t
+    };
+
+    let abstain = Signature::sign_abstain(&mut ballot(), pubkey, privkey);
+    assert!(abstain.verify_abstain(&mut ballot(), pubkey).is_ok());
+    // Not replayable as a vote on the same proposal, nor as an abstention elsewhere.
+    assert!(abstain.verify(&mut ballot(), pubkey).is_err());
+    assert!(abstain
+        .verify_abstain(&mut Transcript::new(b"vote"), pubkey)
+        .is_err());
+
+    let vote = Signature::sign(&mut ballot(), pubkey, privkey);
+    assert!(vote.verify_abstain(&mut ballot(), pubkey).is_err());
+}
+
+#[test]
+fn verify_shared_transcript_finds_bad_signer() {
+    let context = || {
+        let mut t = Transcript::new(b"committee");
+        t.append_message(b"block", b"block 1000");
+        t
+    };
+    let mut entries: Vec<_> = (1..=8u64)
+        .map(|i| {
+            let privkey = Scalar::from(i);
+            let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64 + i));
+            (pubkey, Signature::sign(&mut context(), pubkey, privkey))
+        })
+        .collect();
+    assert!(Signature::verify_shared_transcript(context, &entries).is_ok());
+
+    // One signer signed a different block.
+    let (pubkey, _) = entries[5];
+    let mut other = Transcript::new(b"committee");
+    other.append_message(b"block", b"block 1001");
+    entries[5].1 = Signature::sign(&mut other, pubkey, Scalar::from(6u64));
+    assert_eq!(
+        Signature::verify_shared_transcript(context, &entries),
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+    assert_eq!(
+        Signature::verify_shared_transcript(context, &entries[5..6]),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::verify_shared_transcript(context, &[]),
+        Err(ZkSchnorrError::NoSigners)
+    );
+}
+
+#[test]
+fn batch_with_custom_backend() {
+    use crate::{DalekBackend, MultiscalarBackend};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use std::cell::Cell;
+
+    /// Delegates to dalek, counting calls.
+    #[derive(Default)]
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl MultiscalarBackend for &Counting {
+        fn vartime_multiscalar_mul(
+            &self,
+            scalars: &[Scalar],
+            points: &[RistrettoPoint],
+        ) -> RistrettoPoint {
+            self.calls.set(self.calls.get() + 1);
+            DalekBackend.vartime_multiscalar_mul(scalars, points)
+        }
+    }
+
+    let backend = Counting::default();
+    let mut batch = BatchVerifier::new(rand::thread_rng()).with_backend(&backend);
+    for i in 1..=4u64 {
+        let privkey = Scalar::from(i);
+        let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64 + i));
+        let sig = Signature::sign(&mut Transcript::new(b"backend"), pubkey, privkey);
+        sig.verify_batched(&mut Transcript::new(b"backend"), pubkey, &mut batch);
+    }
+    assert!(batch.is_valid());
+    assert!(batch.verify().is_ok());
+    assert_eq!(backend.calls.get(), 2);
+}
+
+#[test]
+fn batch_combines_points_only_when_asked() {
+    use crate::{DalekBackend, MultiscalarBackend};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use std::cell::Cell;
+
+    /// Delegates to dalek, recording the number of terms of the last call.
+    #[derive(Default)]
+    struct Terms {
+        last: Cell<usize>,
+    }
+
+    impl MultiscalarBackend for &Terms {
+        fn vartime_multiscalar_mul(
+            &self,
+            scalars: &[Scalar],
+            points: &[RistrettoPoint],
+        ) -> RistrettoPoint {
+            self.last.set(points.len());
+            DalekBackend.vartime_multiscalar_mul(scalars, points)
+        }
+    }
+
+    let (privkey, pubkey) = keypair(3);
+    let sigs: Vec<_> = (0..4)
+        .map(|_| Signature::sign(&mut Transcript::new(b"terms"), pubkey, privkey))
+        .collect();
+
+    // Every entry adds `g`, `R` and `h`.
+    let backend = Terms::default();
+    let mut batch = BatchVerifier::new(rand::thread_rng()).with_backend(&backend);
+    for sig in sigs.iter() {
+        sig.verify_batched(&mut Transcript::new(b"terms"), pubkey, &mut batch);
+    }
+    assert_eq!(batch.estimated_cost(), 12);
+    assert!(batch.verify().is_ok());
+    assert_eq!(backend.last.get(), 12);
+
+    // The shared `g` and `h` are multiplied once.
+    let backend = Terms::default();
+    let mut batch = BatchVerifier::new(rand::thread_rng())
+        .with_combined_points()
+        .with_backend(&backend);
+    for sig in sigs.iter() {
+        sig.verify_batched(&mut Transcript::new(b"terms"), pubkey, &mut batch);
+    }
+    assert_eq!(batch.estimated_cost(), 6);
+    assert!(batch.verify().is_ok());
+    assert_eq!(backend.last.get(), 6);
+}
+
+#[test]
+fn key_from_signing_key_and_base() {
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let privkey = Scalar::from(4242u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(777u64));
+    let (g, _) = pubkey.as_point();
+    let rebuilt = VerificationKey::from_signing_key_and_base(&privkey, g).unwrap();
+    assert_eq!(rebuilt, pubkey);
+
+    let bad = CompressedRistretto([0xff; 32]);
+    assert_eq!(
+        VerificationKey::from_signing_key_and_base(&privkey, &bad),
+        Err(ZkSchnorrError::InvalidPoint)
+    );
+}
+
+#[test]
+fn verify_strict_rejects_identity() {
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::traits::Identity;
+
+    let identity = RistrettoPoint::identity().compress();
+    let privkey = Scalar::from(99u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(123u64));
+    let sig = Signature::sign(&mut Transcript::new(b"strict"), pubkey, privkey);
+    assert!(sig.verify_strict(&mut Transcript::new(b"strict"), pubkey).is_ok());
+    assert_eq!(
+        sig.verify_strict(&mut Transcript::new(b"other"), pubkey),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+
+    // A zero signing key gives H at the identity; such signatures pass `verify`.
+    let zero = Scalar::zero();
+    let weak = VerificationKey::from_secret(&zero, &Scalar::from(123u64));
+    let sig = Signature::sign(&mut Transcript::new(b"strict"), weak, zero);
+    assert!(sig.verify(&mut Transcript::new(b"strict"), weak).is_ok());
+    assert_eq!(
+        sig.verify_strict(&mut Transcript::new(b"strict"), weak),
+        Err(ZkSchnorrError::IdentityPoint)
+    );
+
+    // An identity `G` and `H` with an identity `R` and zero `s` satisfy the equation,
+    // but the signature is degenerate.
+    let degenerate_key = VerificationKey::new(identity, identity);
+    let degenerate_sig = Signature::from_parts(identity, Scalar::zero()).unwrap();
+    assert_eq!(
+        degenerate_sig.verify(&mut Transcript::new(b"strict"), degenerate_key),
+        Err(ZkSchnorrError::DegenerateSignature)
+    );
+    assert_eq!(
+        degenerate_sig.verify_strict(&mut Transcript::new(b"strict"), degenerate_key),
+        Err(ZkSchnorrError::IdentityPoint)
+    );
+}
+
+#[test]
+fn aggregate_keys_is_order_independent() {
+    use crate::aggregate_keys;
+
+    let r = Scalar::from(5150u64);
+    let privkeys: Vec<Scalar> = (1..=4u64).map(|i| Scalar::from(i * 1009)).collect();
+    let keys: Vec<VerificationKey> =
+        privkeys.iter().map(|x| VerificationKey::from_secret(x, &r)).collect();
+
+    let (aggregate, weights) = aggregate_keys(&keys).unwrap();
+    let mut reversed = keys.clone();
+    reversed.reverse();
+    let mut rotated = keys.clone();
+    rotated.rotate_left(1);
+    assert_eq!(aggregate_keys(&reversed).unwrap(), (aggregate, weights.clone()));
+    assert_eq!(aggregate_keys(&rotated).unwrap(), (aggregate, weights.clone()));
+    assert!(weights.windows(2).all(|w| w[0].0 <= w[1].0));
+
+    // With a common base, the aggregate secret is Σ a_i·x_i / Σ a_i.
+    let mut numerator = Scalar::zero();
+    let mut denominator = Scalar::zero();
+    for (key, a) in &weights {
+        let i = keys.iter().position(|k| k == key).unwrap();
+        numerator += a * privkeys[i];
+        denominator += a;
+    }
+    let privkey = numerator * denominator.invert();
+    let sig = Signature::sign(&mut Transcript::new(b"aggregate"), aggregate, privkey);
+    assert!(sig.verify_aggregate(weights, &mut Transcript::new(b"aggregate")).is_ok());
+
+    assert_eq!(aggregate_keys(&[]), Err(ZkSchnorrError::NoKeyShares));
+}
+
+#[test]
+fn batch_from_parts() {
+    let sign = |i: u64, label: &'static [u8]| {
+        let privkey = Scalar::from(300 + i);
+        let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(400 + i));
+        let sig = Signature::sign(&mut Transcript::new(b"parts"), pubkey, privkey);
+        (sig, Transcript::new(label), pubkey)
+    };
+    let part = |range: core::ops::Range<u64>, bad: Option<u64>| {
+        let mut batch = BatchVerifier::new(rand::thread_rng());
+        for i in range {
+            let label: &'static [u8] = if Some(i) == bad { b"other" } else { b"parts" };
+            let (sig, mut t, pubkey) = sign(i, label);
+            sig.verify_batched(&mut t, pubkey, &mut batch);
+        }
+        batch
+    };
+
+    let parts = vec![part(0..3, None), part(3..5, None), part(5..9, None)];
+    let merged = BatchVerifier::from_parts(rand::thread_rng(), parts);
+    assert_eq!(merged.validate_keys(), Vec::<usize>::new());
+    assert!(merged.verify().is_ok());
+
+    let parts = vec![part(0..3, None), part(3..5, Some(4)), part(5..9, None)];
+    let mut merged = BatchVerifier::from_parts(rand::thread_rng(), parts);
+    assert!(!merged.is_valid());
+    // Entries appended after merging are weighted with the merged verifier's RNG.
+    let (sig, mut t, pubkey) = sign(9, b"parts");
+    sig.verify_batched(&mut t, pubkey, &mut merged);
+    let bitmap = merged.verify_bitmap().unwrap();
+    assert_eq!(bitmap.len(), 10);
+    assert_eq!(bitmap.iter().position(|valid| !valid), Some(4));
+    assert_eq!(bitmap.iter().filter(|valid| !**valid).count(), 1);
+}
+
+#[test]
+fn counter_bound_signature() {
+    let privkey = Scalar::from(7070u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(8080u64));
+    let n = 1_000_000u64;
+    let sig = Signature::sign_with_counter(b"tx", b"transfer 10", n, pubkey, privkey);
+    assert!(sig.verify_with_counter(b"tx", b"transfer 10", n, pubkey).is_ok());
+    assert_eq!(
+        sig.verify_with_counter(b"tx", b"transfer 10", n + 1, pubkey),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert!(sig.verify_message(b"tx", b"transfer 10", pubkey).is_err());
+}
+
+#[test]
+fn commitment_vector_binds_order_and_length() {
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let privkey = Scalar::from(3141u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(2718u64));
+    let commitments: Vec<CompressedRistretto> = (1..=3u64)
+        .map(|v| (Scalar::from(v) * RISTRETTO_BASEPOINT_POINT).compress())
+        .collect();
+    let sig = Signature::sign_commitments(b"outputs", &commitments, pubkey, privkey);
+    assert!(sig.verify_commitments(b"outputs", &commitments, pubkey).is_ok());
+
+    let mut permuted = commitments.clone();
+    permuted.swap(0, 2);
+    assert_eq!(
+        sig.verify_commitments(b"outputs", &permuted, pubkey),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert!(sig.verify_commitments(b"outputs", &commitments[..2], pubkey).is_err());
+    let mut extended = commitments.clone();
+    extended.push(commitments[0]);
+    assert!(sig.verify_commitments(b"outputs", &extended, pubkey).is_err());
+    assert!(sig.verify_commitment(b"outputs", &commitments[0], pubkey).is_err());
+}
+
+#[test]
+fn verify_split_matches_assembled_signature() {
+    use curve25519_dalek::ristretto::CompressedRistretto;
+
+    let privkey = Scalar::from(6060u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(7070u64));
+    let sig = Signature::sign(&mut Transcript::new(b"split"), pubkey, privkey);
+    let (R, s) = sig.into_parts();
+
+    for label in [&b"split"[..], &b"other"[..]].iter() {
+        let mut t = Transcript::new(b"split");
+        t.append_message(b"label", label);
+        let mut assembled = t.clone();
+        assert_eq!(
+            Signature::verify_split(&mut t, pubkey, &R, &s),
+            sig.verify(&mut assembled, pubkey)
+        );
+    }
+    assert!(Signature::verify_split(&mut Transcript::new(b"split"), pubkey, &R, &s).is_ok());
+    assert_eq!(
+        Signature::verify_split(&mut Transcript::new(b"split"), pubkey, &R, &(s + Scalar::one())),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::verify_split(
+            &mut Transcript::new(b"split"),
+            pubkey,
+            &CompressedRistretto([0xff; 32]),
+            &s
+        ),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::verify_split(
+            &mut Transcript::new(b"split"),
+            pubkey,
+            &R,
+            &Scalar::from_bits([0xff; 32])
+        ),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn request_signature_is_session_bound() {
+    let privkey = Scalar::from(8181u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(9191u64));
+    let body = br#"{"amount":10}"#;
+    let sig = Signature::sign_request(b"req-0001", b"POST /transfer", body, pubkey, privkey);
+    assert!(sig.verify_request(b"req-0001", b"POST /transfer", body, pubkey).is_ok());
+    assert_eq!(
+        sig.verify_request(b"req-0002", b"POST /transfer", body, pubkey),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert!(sig.verify_request(b"req-0001", b"PUT /transfer", body, pubkey).is_err());
+    assert!(sig.verify_request(b"req-0001", b"POST /transfer", b"{}", pubkey).is_err());
+    // Moving bytes between fields does not keep the signature valid.
+    assert!(sig.verify_request(b"req-0001POST", b" /transfer", body, pubkey).is_err());
+}
+
+#[test]
+fn verify_auto_in_every_regime() {
+    use crate::{verify_auto, verify_auto_with, AutoVerifyConfig};
+
+    let items = |n: u64, bad: Option<u64>| {
+        (0..n)
+            .map(|i| {
+                let privkey = Scalar::from(500 + i);
+                let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(600 + i));
+                let sig = Signature::sign(&mut Transcript::new(b"auto"), pubkey, privkey);
+                let label: &'static [u8] = if Some(i) == bad { b"other" } else { b"auto" };
+                (Transcript::new(label), pubkey, sig)
+            })
+            .collect::<Vec<_>>()
+    };
+    let config = AutoVerifyConfig {
+        batch_threshold: 3,
+        parallel_threshold: 8,
+    };
+
+    assert!(verify_auto_with(&[], &config).is_ok());
+    let zero = AutoVerifyConfig {
+        batch_threshold: 0,
+        parallel_threshold: 0,
+    };
+    assert!(verify_auto_with(&[], &zero).is_ok());
+    assert!(verify_auto_with(&items(1, None), &zero).is_ok());
+    // One by one, in a single batch, and in parallel sub-batches.
+    for (n, err) in [
+        (2, ZkSchnorrError::InvalidSignature),
+        (5, ZkSchnorrError::InvalidBatch),
+        (20, ZkSchnorrError::InvalidBatch),
+    ] {
+        assert!(verify_auto_with(&items(n, None), &config).is_ok());
+        assert_eq!(verify_auto_with(&items(n, Some(n - 1)), &config), Err(err.clone()));
+        assert_eq!(verify_auto_with(&items(n, Some(0)), &config), Err(err));
+    }
+
+    assert!(verify_auto(&items(4, None)).is_ok());
+    assert_eq!(verify_auto(&items(4, Some(2))), Err(ZkSchnorrError::InvalidBatch));
+}
+
+#[test]
+fn context_signs_and_verifies() {
+    use crate::Context;
+
+    let privkey = Scalar::from(5656u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(7878u64));
+    let context = |amount: u64| {
+        Context::new(b"payments.transfer")
+            .append(b"recipient", b"alice")
+            .append(b"amount", amount.to_le_bytes())
+    };
+
+    let sig = context(10).sign(pubkey, privkey);
+    // The verifier builds its own context from the same inputs.
+    assert!(context(10).verify(&sig, pubkey).is_ok());
+    assert_eq!(context(11).verify(&sig, pubkey), Err(ZkSchnorrError::InvalidSignature));
+    assert!(Context::new(b"payments.refund")
+        .append(b"recipient", b"alice")
+        .append(b"amount", 10u64.to_le_bytes())
+        .verify(&sig, pubkey)
+        .is_err());
+
+    // Equivalent to signing the transcript by hand.
+    assert!(sig.verify(&mut context(10).transcript(), pubkey).is_ok());
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    context(10).verify_batched(&sig, pubkey, &mut batch);
+    assert!(batch.verify().is_ok());
+}
+
+#[test]
+fn degenerate_for_key() {
+    use crate::compute_challenge;
+    use curve25519_dalek::ristretto::CompressedRistretto;
+    use curve25519_dalek::traits::Identity;
+
+    let privkey = Scalar::from(4040u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(5050u64));
+    let sig = Signature::sign(&mut Transcript::new(b"ingest"), pubkey, privkey);
+    assert!(!sig.is_degenerate_for(&pubkey));
+
+    // A nonce of one gives `R == G`, and the signature still verifies.
+    let (g, h) = pubkey.as_point();
+    let c = compute_challenge(&mut Transcript::new(b"ingest"), &pubkey, g);
+    let unit_nonce = Signature::from_parts(*g, Scalar::one() + c * privkey).unwrap();
+    assert!(unit_nonce.verify(&mut Transcript::new(b"ingest"), pubkey).is_ok());
+    assert!(unit_nonce.is_degenerate_for(&pubkey));
+
+    // Likewise a nonce equal to the signing key gives `R == H`.
+    let c = compute_challenge(&mut Transcript::new(b"ingest"), &pubkey, h);
+    let key_nonce = Signature::from_parts(*h, privkey + c * privkey).unwrap();
+    assert!(key_nonce.verify(&mut Transcript::new(b"ingest"), pubkey).is_ok());
+    assert!(key_nonce.is_degenerate_for(&pubkey));
+
+    let identity = Signature::from_parts(CompressedRistretto::identity(), sig.s()).unwrap();
+    assert!(identity.is_degenerate_for(&pubkey));
+}
+
+#[test]
+fn single_verifier_requires_every_relation() {
+    use crate::{BatchVerification, SingleVerifier};
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as B;
+
+    let privkey = Scalar::from(5u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+    let holds = ([Scalar::from(2u64), -Scalar::one()], [B, B + B]);
+
+    assert!(SingleVerifier::verify(|v| {
+        sig.verify_batched(&mut Transcript::new(b"example transcript"), pubkey, v);
+        v.append_relation(holds.0, holds.1);
+    })
+    .is_ok());
+    // A later relation that holds does not hide an earlier failure.
+    assert_eq!(
+        SingleVerifier::verify(|v| {
+            sig.verify_batched(&mut Transcript::new(b"wrong message"), pubkey, v);
+            v.append_relation(holds.0, holds.1);
+        }),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        SingleVerifier::verify(|_| {}),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
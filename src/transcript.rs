@@ -2,6 +2,105 @@ use curve25519_dalek::ristretto::CompressedRistretto;
 use curve25519_dalek::scalar::Scalar;
 use merlin::Transcript;
 
+/// Label of the domain separator message, appended first when computing a challenge.
+pub const DOMAIN_SEP_LABEL: &[u8] = b"dom-sep";
+/// Domain separator of the signature protocol, appended under `DOMAIN_SEP_LABEL`.
+pub const SIGNING_DOMAIN: &[u8] = b"ElGamalSign v1";
+/// Domain separator of short signatures, appended under `DOMAIN_SEP_LABEL`.
+pub const SHORT_SIGNING_DOMAIN: &[u8] = b"ElGamalSign short v1";
+/// Label of the compressed verification key point `G`, appended after the domain separator.
+pub const G_LABEL: &[u8] = b"G";
+/// Label of the compressed verification key point `H`, appended after `G`.
+pub const H_LABEL: &[u8] = b"H";
+/// Label of the compressed nonce commitment `R`, appended after `H`.
+pub const R_LABEL: &[u8] = b"R";
+/// Label of the challenge squeezed from the transcript after `R`.
+pub const CHALLENGE_LABEL: &[u8] = b"challenge";
+/// Label of the transcript created by the message API (`Signature::sign_message`),
+/// to which the message is appended under the caller's label.
+pub const MESSAGE_TRANSCRIPT_LABEL: &[u8] = b"Elgamal.sign_message";
+/// Label of the transcript created by `Signature::sign_in_protocol`,
+/// to which the protocol identifier is appended before the message.
+pub const PROTOCOL_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_in_protocol";
+/// Label of the protocol identifier in transcripts of `Signature::sign_in_protocol`.
+pub const PROTOCOL_ID_LABEL: &[u8] = b"protocol-id";
+/// Label of the transcript created by `Signature::sign_prehashed`,
+/// to which the 64-byte digest of the message is appended.
+pub const PREHASH_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_prehashed";
+/// Label of the digest algorithm identifier in transcripts of `Signature::sign_prehashed_with`,
+/// appended before the digest.
+pub const PREHASH_ALGORITHM_LABEL: &[u8] = b"prehash";
+/// Label of the transcript created by `Signature::sign_message_reader`, to which the
+/// message is appended in chunks of `MESSAGE_CHUNK_LEN` bytes.
+pub const READER_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_message_reader";
+/// Length of the chunks a streamed message is split into; only the last chunk may be shorter.
+pub const MESSAGE_CHUNK_LEN: usize = 64 * 1024;
+/// Label of the reserved token appended by `Signature::sign_abstain`.
+pub const ABSTAIN_LABEL: &[u8] = b"abstain";
+/// Reserved token recording an explicit abstention, appended under `ABSTAIN_LABEL`.
+pub const ABSTAIN_TOKEN: &[u8] = b"ZkSchnorr abstain v1";
+/// Label of the transcript created by `aggregate_keys` to derive aggregation coefficients.
+pub const KEY_AGGREGATION_LABEL: &[u8] = b"ZkSchnorr.aggregate_keys";
+/// Label of the aggregation coefficient squeezed for each key by `aggregate_keys`.
+pub const KEY_COEFFICIENT_LABEL: &[u8] = b"coefficient";
+/// Label of the transcript created by `Signature::sign_with_counter`,
+/// to which the counter is appended before the message.
+pub const COUNTER_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_with_counter";
+/// Label of the counter in transcripts of `Signature::sign_with_counter`.
+pub const COUNTER_LABEL: &[u8] = b"counter";
+/// Label of the transcript created by `Signature::sign_commitments`,
+/// to which the number of commitments and then each indexed commitment are appended.
+pub const COMMITMENTS_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_commitments";
+/// Label of the number of commitments in transcripts of `Signature::sign_commitments`.
+pub const COMMITMENT_COUNT_LABEL: &[u8] = b"n";
+/// Label of the index appended before each commitment by `Signature::sign_commitments`.
+pub const COMMITMENT_INDEX_LABEL: &[u8] = b"index";
+/// Label of the transcript created by `Signature::sign_request`, to which the session ID,
+/// method and body of the request are appended.
+pub const REQUEST_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_request";
+/// Label of the session or request ID in transcripts of `Signature::sign_request`.
+pub const SESSION_ID_LABEL: &[u8] = b"session-id";
+/// Label of the request method in transcripts of `Signature::sign_request`.
+pub const REQUEST_METHOD_LABEL: &[u8] = b"method";
+/// Label of the request body in transcripts of `Signature::sign_request`.
+pub const REQUEST_BODY_LABEL: &[u8] = b"body";
+/// Label of the transcript created by `Signature::sign_signable`, to which the value
+/// is appended with `Signable::to_transcript`.
+pub const SIGNABLE_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_signable";
+/// Label of the transcript created by `Signature::sign_message_key_committing`, to which
+/// the verification key is appended before the message.
+pub const KEY_COMMITTING_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_message_key_committing";
+/// Label of the transcript created by `Signature::sign_commitment`, to which the
+/// compressed commitment is appended under the caller's label.
+pub const COMMITMENT_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.sign_commitment";
+/// Label of the transcript created by `countersign`, to which the original key and
+/// signature are appended.
+pub const COUNTERSIGN_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.countersign";
+/// Label of the transcript signed by each link of a key chain, to which the new key is appended.
+pub const KEY_ROTATION_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.key_rotation";
+/// Label of the transcript of `VerificationKey::prove_structure` and `verify_structure`.
+pub const KEY_STRUCTURE_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.key_structure";
+/// Label of the transcript hashed by `VerificationKey::fingerprint`.
+pub const FINGERPRINT_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.fingerprint";
+/// Label of the transcript created by `hash_to_scalar`.
+pub const HASH_TO_SCALAR_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.hash_to_scalar";
+/// Label of the transcript from which `Signature::sign_with_challenge_fn` derives its nonce.
+pub const CHALLENGE_FN_TRANSCRIPT_LABEL: &[u8] = b"ZkSchnorr.challenge_fn";
+/// Label under which `VerificationCache` squeezes a digest of the transcript state.
+pub const CACHE_DIGEST_LABEL: &[u8] = b"ZkSchnorr.cache";
+
+/// Hashes `input` into a scalar, separated by `domain`, so that every feature deriving
+/// scalars from bytes (e.g. from seeds) does so the same way. Internally it creates a
+/// Transcript instance labelled "ZkSchnorr.hash_to_scalar", appends the `domain` and
+/// the `input` (labelled "domain" and "input"), and squeezes the challenge scalar
+/// labelled "scalar". Different domains yield independent scalars for the same input.
+pub fn hash_to_scalar(domain: &[u8], input: &[u8]) -> Scalar {
+    let mut t = Transcript::new(HASH_TO_SCALAR_TRANSCRIPT_LABEL);
+    t.append_message(b"domain", domain);
+    t.append_message(b"input", input);
+    t.challenge_scalar(b"scalar")
+}
+
 /// Extension trait to the Merlin transcript API that allows committing scalars and points and
 /// generating challenges as scalars.
 pub trait TranscriptProtocol {
@@ -17,7 +116,7 @@ pub trait TranscriptProtocol {
 
 impl TranscriptProtocol for Transcript {
     fn zkschnorr_domain_sep(&mut self) {
-        self.append_message(b"dom-sep", b"ElGamalSign v1");
+        self.append_message(DOMAIN_SEP_LABEL, SIGNING_DOMAIN);
     }
 
     fn append_scalar(&mut self, label: &'static [u8], scalar: &Scalar) {
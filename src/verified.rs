@@ -0,0 +1,65 @@
+use merlin::Transcript;
+
+use super::errors::ZkSchnorrError;
+use super::key::VerificationKey;
+use super::signature::Signature;
+
+/// Proof that a signature was verified under the contained key.
+///
+/// A `Verified` can only be obtained from a successful verification, so code
+/// taking one as an argument can rely on the key having signed the message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Verified {
+    key: VerificationKey,
+}
+
+impl Verified {
+    /// Returns the key the signature was verified under.
+    pub fn key(&self) -> &VerificationKey {
+        &self.key
+    }
+
+    /// Consumes the token, returning the key the signature was verified under.
+    pub fn into_inner(self) -> VerificationKey {
+        self.key
+    }
+}
+
+impl Signature {
+    /// Verifies the signature like `verify`, returning a `Verified` token
+    /// holding `pubkey` on success.
+    pub fn verify_token(
+        &self,
+        transcript: &mut Transcript,
+        pubkey: VerificationKey,
+    ) -> Result<Verified, ZkSchnorrError> {
+        self.verify(transcript, pubkey)?;
+        Ok(Verified { key: pubkey })
+    }
+
+    /// Verifies the signature over a message like `verify_message`, returning a
+    /// `Verified` token holding `pubkey` on success.
+    pub fn verify_message_token(
+        &self,
+        label: &'static [u8],
+        message: &[u8],
+        pubkey: VerificationKey,
+    ) -> Result<Verified, ZkSchnorrError> {
+        self.verify_message(label, message, pubkey)?;
+        Ok(Verified { key: pubkey })
+    }
+
+    /// Verifies the signature against each of `pubkeys` in turn, returning a
+    /// `Verified` token holding the first key that matches.
+    /// Each attempt starts from a copy of `transcript`, which is left untouched.
+    pub fn verify_any(
+        &self,
+        transcript: &Transcript,
+        pubkeys: &[VerificationKey],
+    ) -> Result<Verified, ZkSchnorrError> {
+        pubkeys
+            .iter()
+            .find_map(|pubkey| self.verify_token(&mut transcript.clone(), *pubkey).ok())
+            .ok_or(ZkSchnorrError::InvalidSignature)
+    }
+}
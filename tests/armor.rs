@@ -0,0 +1,51 @@
+#![cfg(feature = "armor")]
+
+use curve25519_dalek::scalar::Scalar;
+use zkschnorr::{VerificationKey, ZkSchnorrError};
+
+const LABEL: &str = "ZKSCHNORR VERIFICATION KEY";
+
+fn key() -> VerificationKey {
+    VerificationKey::from_secret(&Scalar::from(8675309u64), &Scalar::from(42u64))
+}
+
+#[test]
+fn armored_round_trip() {
+    let armored = key().to_armored(LABEL);
+    let lines: Vec<&str> = armored.lines().collect();
+    assert_eq!(lines.first(), Some(&"-----BEGIN ZKSCHNORR VERIFICATION KEY-----"));
+    assert_eq!(lines.last(), Some(&"-----END ZKSCHNORR VERIFICATION KEY-----"));
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[1].len(), 64);
+
+    assert_eq!(VerificationKey::from_armored(&armored, LABEL).unwrap(), key());
+    // Pasted text may be indented or carry surrounding whitespace.
+    let pasted = format!("\n  {}  \n", armored.replace('\n', "\n    "));
+    assert_eq!(VerificationKey::from_armored(&pasted, LABEL).unwrap(), key());
+}
+
+#[test]
+fn armored_rejects_malformed_text() {
+    let armored = key().to_armored(LABEL);
+    assert_eq!(
+        VerificationKey::from_armored(&armored, "OTHER KEY"),
+        Err(ZkSchnorrError::InvalidArmor)
+    );
+    let no_footer: String = armored.lines().take(3).collect::<Vec<_>>().join("\n");
+    assert_eq!(
+        VerificationKey::from_armored(&no_footer, LABEL),
+        Err(ZkSchnorrError::InvalidArmor)
+    );
+    let truncated = armored.replacen("=\n", "\n", 1);
+    assert_eq!(
+        VerificationKey::from_armored(&truncated, LABEL),
+        Err(ZkSchnorrError::InvalidArmor)
+    );
+    let mut lines: Vec<String> = armored.lines().map(String::from).collect();
+    lines[1].replace_range(..1, "*");
+    let not_base64 = lines.join("\n");
+    assert_eq!(
+        VerificationKey::from_armored(&not_base64, LABEL),
+        Err(ZkSchnorrError::InvalidArmor)
+    );
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "base32")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{Signature, VerificationKey, ZkSchnorrError, BASE32_LEN};
+
+fn keypair_and_signature() -> (VerificationKey, Signature) {
+    let privkey = Scalar::from(31337u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(271828u64));
+    let sig = Signature::sign(&mut Transcript::new(b"base32"), pubkey, privkey);
+    (pubkey, sig)
+}
+
+fn is_qr_alphanumeric(s: &str) -> bool {
+    s.chars().all(|c| c.is_ascii_digit() || c.is_ascii_uppercase())
+}
+
+#[test]
+fn signature_round_trip() {
+    let (pubkey, sig) = keypair_and_signature();
+    let encoded = sig.to_base32();
+    assert_eq!(encoded.len(), BASE32_LEN);
+    assert!(is_qr_alphanumeric(&encoded));
+
+    let decoded = Signature::from_base32(&encoded).unwrap();
+    assert_eq!(decoded, sig);
+    assert!(decoded.verify(&mut Transcript::new(b"base32"), pubkey).is_ok());
+}
+
+#[test]
+fn key_round_trip() {
+    let (pubkey, _) = keypair_and_signature();
+    let encoded = pubkey.to_base32();
+    assert_eq!(encoded.len(), BASE32_LEN);
+    assert!(is_qr_alphanumeric(&encoded));
+    assert_eq!(VerificationKey::from_base32(&encoded).unwrap(), pubkey);
+}
+
+#[test]
+fn rejects_wrong_length_and_alphabet() {
+    let (pubkey, sig) = keypair_and_signature();
+    let encoded = sig.to_base32();
+    assert_eq!(
+        Signature::from_base32(&encoded[..BASE32_LEN - 1]),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::from_base32(&format!("{}A", encoded)),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::from_base32(&encoded.to_lowercase()),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        VerificationKey::from_base32(&pubkey.to_base32()[1..]),
+        Err(ZkSchnorrError::InvalidPoint)
+    );
+}
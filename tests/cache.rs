@@ -0,0 +1,83 @@
+#![cfg(feature = "cache")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{Signature, VerificationCache, VerificationKey};
+
+fn transcript(message: &[u8]) -> Transcript {
+    let mut t = Transcript::new(b"cache");
+    t.append_message(b"message", message);
+    t
+}
+
+#[test]
+fn cached_result_matches_fresh_verification() {
+    let privkey = Scalar::from(1234u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(5678u64));
+    let sig = Signature::sign(&mut transcript(b"retry"), pubkey, privkey);
+    let mut cache = VerificationCache::new(8);
+
+    let mut fresh = transcript(b"retry");
+    let expected = sig.verify(&mut fresh, pubkey);
+    assert!(expected.is_ok());
+
+    let mut first = transcript(b"retry");
+    assert_eq!(cache.verify(&sig, &mut first, pubkey), expected);
+    assert_eq!(cache.hits(), 0);
+    let mut second = transcript(b"retry");
+    assert_eq!(cache.verify(&sig, &mut second, pubkey), expected);
+    assert_eq!(cache.hits(), 1);
+
+    // A hit leaves the transcript in the same state as a fresh verification.
+    let mut a = [0u8; 32];
+    let mut b = [0u8; 32];
+    fresh.challenge_bytes(b"next", &mut a);
+    second.challenge_bytes(b"next", &mut b);
+    assert_eq!(a, b);
+
+    // Failures are cached too.
+    let other = VerificationKey::from_secret(&Scalar::from(1u64), &Scalar::from(2u64));
+    let failed = sig.verify(&mut transcript(b"retry"), other);
+    assert!(failed.is_err());
+    assert_eq!(cache.verify(&sig, &mut transcript(b"retry"), other), failed);
+    assert_eq!(cache.verify(&sig, &mut transcript(b"retry"), other), failed);
+    assert_eq!(cache.hits(), 2);
+}
+
+#[test]
+fn changed_message_misses() {
+    let privkey = Scalar::from(4321u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(8765u64));
+    let sig = Signature::sign(&mut transcript(b"original"), pubkey, privkey);
+    let mut cache = VerificationCache::new(8);
+
+    assert!(cache.verify(&sig, &mut transcript(b"original"), pubkey).is_ok());
+    assert!(cache.verify(&sig, &mut transcript(b"tampered"), pubkey).is_err());
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    let privkey = Scalar::from(99u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(100u64));
+    let messages: [&[u8]; 3] = [b"a", b"b", b"c"];
+    let sigs: Vec<Signature> = messages
+        .iter()
+        .map(|m| Signature::sign(&mut transcript(m), pubkey, privkey))
+        .collect();
+    let mut cache = VerificationCache::new(2);
+
+    assert!(cache.verify(&sigs[0], &mut transcript(b"a"), pubkey).is_ok());
+    assert!(cache.verify(&sigs[1], &mut transcript(b"b"), pubkey).is_ok());
+    // Touch "a" so that "b" becomes the least recently used entry.
+    assert!(cache.verify(&sigs[0], &mut transcript(b"a"), pubkey).is_ok());
+    assert!(cache.verify(&sigs[2], &mut transcript(b"c"), pubkey).is_ok());
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.hits(), 1);
+
+    assert!(cache.verify(&sigs[0], &mut transcript(b"a"), pubkey).is_ok());
+    assert_eq!(cache.hits(), 2);
+    assert!(cache.verify(&sigs[1], &mut transcript(b"b"), pubkey).is_ok());
+    assert_eq!(cache.hits(), 2);
+}
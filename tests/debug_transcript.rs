@@ -0,0 +1,67 @@
+#![cfg(feature = "debug-transcript")]
+
+use curve25519_dalek::scalar::Scalar;
+use zkschnorr::{
+    RecordedVerifyError, RecordingTranscript, Signature, TranscriptDivergence, VerificationKey,
+    ZkSchnorrError,
+};
+
+fn transcript(label: &'static [u8], appends: &[(&'static [u8], &[u8])]) -> RecordingTranscript {
+    let mut t = RecordingTranscript::new(label);
+    for (label, message) in appends {
+        t.append_message(label, message);
+    }
+    t
+}
+
+#[test]
+fn reports_first_divergence() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let (sig, log) = Signature::sign_recorded(
+        &mut transcript(b"app", &[(b"amount", b"10"), (b"memo", b"hi")]),
+        pubkey,
+        privkey,
+    );
+    assert_eq!(log.appends(), &[(&b"amount"[..], 2), (&b"memo"[..], 2)]);
+
+    assert!(sig
+        .verify_recorded(&mut transcript(b"app", &[(b"amount", b"10"), (b"memo", b"hi")]), pubkey, &log)
+        .is_ok());
+
+    // Swapped fields.
+    assert_eq!(
+        sig.verify_recorded(&mut transcript(b"app", &[(b"memo", b"hi"), (b"amount", b"10")]), pubkey, &log),
+        Err(RecordedVerifyError::Diverged(TranscriptDivergence::Append {
+            index: 0,
+            signer: Some((b"amount", 2)),
+            verifier: Some((b"memo", 2)),
+        }))
+    );
+
+    // Missing field.
+    assert_eq!(
+        sig.verify_recorded(&mut transcript(b"app", &[(b"amount", b"10")]), pubkey, &log),
+        Err(RecordedVerifyError::Diverged(TranscriptDivergence::Append {
+            index: 1,
+            signer: Some((b"memo", 2)),
+            verifier: None,
+        }))
+    );
+
+    // Different transcript label.
+    let err = sig
+        .verify_recorded(&mut transcript(b"other", &[(b"amount", b"10"), (b"memo", b"hi")]), pubkey, &log)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Signature verification failed: transcript label \"other\" differs from signer's \"app\""
+    );
+
+    // Same shape but different contents cannot be told apart.
+    assert_eq!(
+        sig.verify_recorded(&mut transcript(b"app", &[(b"amount", b"11"), (b"memo", b"hi")]), pubkey, &log),
+        Err(RecordedVerifyError::Failed(ZkSchnorrError::InvalidSignature))
+    );
+    assert_eq!(ZkSchnorrError::from(err), ZkSchnorrError::InvalidSignature);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "derive")]
+
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{Signable, Signature, VerificationKey};
+
+#[derive(Signable, Clone)]
+struct Transfer {
+    recipient: VerificationKey,
+    amount: u64,
+    commitment: CompressedRistretto,
+    memo: String,
+    fee: Fee,
+}
+
+#[derive(Signable, Clone)]
+struct Fee(u32, bool);
+
+fn transfer() -> Transfer {
+    Transfer {
+        recipient: VerificationKey::from_secret(&Scalar::from(9u64), &Scalar::from(10987u64)),
+        amount: 1000,
+        commitment: CompressedRistretto([0u8; 32]),
+        memo: "rent".to_string(),
+        fee: Fee(5, true),
+    }
+}
+
+#[test]
+fn signable_round_trip() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let transfer = transfer();
+
+    let sig = Signature::sign_signable(&transfer, pubkey, privkey);
+    assert!(sig.verify_signable(&transfer, pubkey).is_ok());
+
+    let mut changed = transfer.clone();
+    changed.amount += 1;
+    assert!(sig.verify_signable(&changed, pubkey).is_err());
+    let mut changed = transfer.clone();
+    changed.fee.1 = false;
+    assert!(sig.verify_signable(&changed, pubkey).is_err());
+}
+
+#[test]
+fn derived_transcript_layout() {
+    let transfer = transfer();
+    let mut derived = Transcript::new(b"layout");
+    transfer.to_transcript(&mut derived);
+
+    let mut manual = Transcript::new(b"layout");
+    manual.append_message(b"type", b"Transfer");
+    manual.append_message(b"recipient", &transfer.recipient.to_bytes_array());
+    manual.append_message(b"amount", &1000u64.to_le_bytes());
+    manual.append_message(b"commitment", &[0u8; 32]);
+    manual.append_message(b"memo", b"rent");
+    manual.append_message(b"fee", b"");
+    manual.append_message(b"type", b"Fee");
+    manual.append_message(b"0", &5u32.to_le_bytes());
+    manual.append_message(b"1", &[1]);
+
+    let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+    derived.challenge_bytes(b"check", &mut a);
+    manual.challenge_bytes(b"check", &mut b);
+    assert_eq!(a, b);
+}
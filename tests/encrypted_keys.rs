@@ -0,0 +1,70 @@
+#![cfg(feature = "encrypted-keys")]
+
+use curve25519_dalek::scalar::Scalar;
+use zkschnorr::{SigningKey, SigningKeySeal, ZkSchnorrError};
+
+// Low scrypt cost to keep the tests fast.
+fn seal(key: &SigningKey, passphrase: &[u8]) -> Vec<u8> {
+    key.seal_with_params(passphrase, 4, 8, 1).unwrap()
+}
+
+#[test]
+fn sealed_key_round_trip() {
+    let privkey = Scalar::from(1234567u64);
+    let sealed = seal(&privkey, b"correct horse");
+    assert_eq!(sealed[0], 1);
+    assert_eq!(SigningKey::open(&sealed, b"correct horse"), Ok(privkey));
+
+    // Fresh salt and nonce for every sealing.
+    assert_ne!(seal(&privkey, b"correct horse"), sealed);
+
+    let sealed = privkey.seal(b"correct horse");
+    assert_eq!(SigningKey::open(&sealed, b"correct horse"), Ok(privkey));
+}
+
+#[test]
+fn wrong_passphrase_is_rejected() {
+    let sealed = seal(&Scalar::from(1234567u64), b"correct horse");
+    assert_eq!(
+        SigningKey::open(&sealed, b"battery staple"),
+        Err(ZkSchnorrError::InvalidSealedKey)
+    );
+}
+
+#[test]
+fn tampered_sealed_key_is_rejected() {
+    let sealed = seal(&Scalar::from(1234567u64), b"correct horse");
+
+    // The header, including the KDF parameters, is authenticated.
+    for i in [0, 1, 12, 30, sealed.len() - 1].iter() {
+        let mut tampered = sealed.clone();
+        tampered[*i] ^= 1;
+        assert!(SigningKey::open(&tampered, b"correct horse").is_err());
+    }
+    assert!(SigningKey::open(&sealed[..sealed.len() - 1], b"correct horse").is_err());
+    assert_eq!(
+        Scalar::from(1u64).seal_with_params(b"pw", 4, 0, 1),
+        Err(ZkSchnorrError::InvalidSealedKey)
+    );
+}
+
+#[test]
+fn excessive_scrypt_parameters_are_rejected() {
+    let sealed = seal(&Scalar::from(1234567u64), b"correct horse");
+
+    // The parameters are checked before the (unbounded) key derivation runs.
+    for (log_n, r, p) in [(40, 8, 1), (21, 1, 1), (4, u32::MAX, u32::MAX), (4, 8, 2)].iter() {
+        let mut crafted = sealed.clone();
+        crafted[1] = *log_n;
+        crafted[2..6].copy_from_slice(&r.to_le_bytes());
+        crafted[6..10].copy_from_slice(&p.to_le_bytes());
+        assert_eq!(
+            SigningKey::open(&crafted, b"correct horse"),
+            Err(ZkSchnorrError::InvalidSealedKey)
+        );
+    }
+    assert_eq!(
+        Scalar::from(1u64).seal_with_params(b"pw", 21, 8, 1),
+        Err(ZkSchnorrError::InvalidSealedKey)
+    );
+}
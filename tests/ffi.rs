@@ -0,0 +1,124 @@
+#![cfg(feature = "ffi")]
+
+use core::ptr;
+use zkschnorr::ffi::*;
+use zkschnorr::{Signature, VerificationKey};
+
+fn keypair() -> ([u8; 32], [u8; 64]) {
+    let mut privkey = [0u8; 32];
+    let mut pubkey = [0u8; 64];
+    assert_eq!(
+        unsafe { zkschnorr_generate_keypair(privkey.as_mut_ptr(), pubkey.as_mut_ptr()) },
+        ZKSCHNORR_OK
+    );
+    (privkey, pubkey)
+}
+
+fn sign(privkey: &[u8; 32], pubkey: &[u8; 64], message: &[u8]) -> [u8; 64] {
+    let mut sig = [0u8; 64];
+    let code = unsafe {
+        zkschnorr_sign(
+            privkey.as_ptr(),
+            pubkey.as_ptr(),
+            message.as_ptr(),
+            message.len(),
+            sig.as_mut_ptr(),
+        )
+    };
+    assert_eq!(code, ZKSCHNORR_OK);
+    sig
+}
+
+fn verify(pubkey: &[u8; 64], message: &[u8], sig: &[u8; 64]) -> i32 {
+    unsafe { zkschnorr_verify(pubkey.as_ptr(), message.as_ptr(), message.len(), sig.as_ptr()) }
+}
+
+#[test]
+fn sign_and_verify() {
+    let (privkey, pubkey) = keypair();
+    let sig = sign(&privkey, &pubkey, b"hello");
+    assert_eq!(verify(&pubkey, b"hello", &sig), ZKSCHNORR_OK);
+    assert_eq!(verify(&pubkey, b"bye", &sig), ZKSCHNORR_ERR_INVALID_SIGNATURE);
+
+    // Interoperates with the message API.
+    let rust_sig = Signature::from_bytes(&sig[..]).unwrap();
+    let rust_key = VerificationKey::from_bytes(&pubkey).unwrap();
+    assert!(rust_sig
+        .verify_message(FFI_MESSAGE_LABEL, b"hello", rust_key)
+        .is_ok());
+
+    // Empty messages may be passed as null.
+    let sig = sign(&privkey, &pubkey, b"");
+    assert_eq!(
+        unsafe { zkschnorr_verify(pubkey.as_ptr(), ptr::null(), 0, sig.as_ptr()) },
+        ZKSCHNORR_OK
+    );
+}
+
+#[test]
+fn invalid_inputs() {
+    let (privkey, pubkey) = keypair();
+    let sig = sign(&privkey, &pubkey, b"hello");
+    let mut out = [0u8; 64];
+
+    assert_eq!(
+        unsafe { zkschnorr_generate_keypair(ptr::null_mut(), out.as_mut_ptr()) },
+        ZKSCHNORR_ERR_NULL_POINTER
+    );
+    assert_eq!(
+        unsafe { zkschnorr_verify(pubkey.as_ptr(), ptr::null(), 5, sig.as_ptr()) },
+        ZKSCHNORR_ERR_NULL_POINTER
+    );
+    assert_eq!(
+        unsafe { zkschnorr_sign(privkey.as_ptr(), pubkey.as_ptr(), ptr::null(), 0, ptr::null_mut()) },
+        ZKSCHNORR_ERR_NULL_POINTER
+    );
+
+    let non_canonical = [0xffu8; 32];
+    assert_eq!(
+        unsafe { zkschnorr_sign(non_canonical.as_ptr(), pubkey.as_ptr(), ptr::null(), 0, out.as_mut_ptr()) },
+        ZKSCHNORR_ERR_INVALID_SIGNING_KEY
+    );
+    let bad_key = [0xffu8; 64];
+    assert_eq!(
+        unsafe { zkschnorr_sign(privkey.as_ptr(), bad_key.as_ptr(), ptr::null(), 0, out.as_mut_ptr()) },
+        ZKSCHNORR_ERR_INVALID_VERIFICATION_KEY
+    );
+    assert_eq!(verify(&bad_key, b"hello", &sig), ZKSCHNORR_ERR_INVALID_VERIFICATION_KEY);
+}
+
+#[test]
+fn batch_verify() {
+    let entries: Vec<_> = (0..3)
+        .map(|i| {
+            let (privkey, pubkey) = keypair();
+            let message = vec![i as u8; i + 1];
+            let sig = sign(&privkey, &pubkey, &message);
+            (pubkey, message, sig)
+        })
+        .collect();
+
+    let run = |entries: &[([u8; 64], Vec<u8>, [u8; 64])]| {
+        let keys: Vec<u8> = entries.iter().flat_map(|e| e.0.to_vec()).collect();
+        let messages: Vec<*const u8> = entries.iter().map(|e| e.1.as_ptr()).collect();
+        let lens: Vec<usize> = entries.iter().map(|e| e.1.len()).collect();
+        let sigs: Vec<u8> = entries.iter().flat_map(|e| e.2.to_vec()).collect();
+        unsafe {
+            zkschnorr_batch_verify(
+                keys.as_ptr(),
+                messages.as_ptr(),
+                lens.as_ptr(),
+                sigs.as_ptr(),
+                entries.len(),
+            )
+        }
+    };
+
+    assert_eq!(run(&entries), ZKSCHNORR_OK);
+    assert_eq!(run(&[]), ZKSCHNORR_OK);
+
+    let mut bad = entries.clone();
+    bad[1].1.push(0);
+    assert_eq!(run(&bad), ZKSCHNORR_ERR_INVALID_BATCH);
+    assert_eq!(run(&bad[1..2]), ZKSCHNORR_ERR_INVALID_SIGNATURE);
+}
@@ -0,0 +1,16 @@
+#![cfg(feature = "getrandom")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{Signature, VerificationKey};
+
+#[test]
+fn sign_with_system_rng() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+    assert!(sig
+        .verify(&mut Transcript::new(b"example transcript"), pubkey)
+        .is_ok());
+}
@@ -0,0 +1,67 @@
+//! Byte-exact layout of keys and signatures, pinned for ports to other languages.
+//! Complements the vectors in `vectors.json`: the key below is `x = 7`, `r = 11`,
+//! and the signature is made with `sign_with_rng` and a `ChaCha20Rng` seeded with
+//! 32 zero bytes over a transcript labelled "layout" with message "hello" (labelled
+//! "message").
+//! If any assertion changes, the serialization contract has changed.
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use rand_chacha::ChaCha20Rng;
+use rand::SeedableRng;
+use zkschnorr::{Signature, VerificationKey};
+
+fn signature() -> (Scalar, VerificationKey, Signature) {
+    let privkey = Scalar::from(7u64);
+    let r = Scalar::from(11u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &r);
+
+    let mut transcript = Transcript::new(b"layout");
+    transcript.append_message(b"message", b"hello");
+    let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+    let sig = Signature::sign_with_rng(&mut transcript, pubkey, privkey, &mut rng);
+    (privkey, pubkey, sig)
+}
+
+#[test]
+fn scalar_is_little_endian() {
+    let (privkey, _, _) = signature();
+    assert_eq!(
+        hex::encode(privkey.to_bytes()),
+        "0700000000000000000000000000000000000000000000000000000000000000"
+    );
+}
+
+#[test]
+fn key_with_unit_randomness_starts_with_basepoint() {
+    let pubkey = VerificationKey::from_secret(&Scalar::from(2u64), &Scalar::one());
+    assert_eq!(
+        hex::encode(&pubkey.to_bytes_array()[..32]),
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76"
+    );
+}
+
+#[test]
+fn verification_key_layout() {
+    let (_, pubkey, _) = signature();
+    // G = 11·B || H = 7·G
+    let expected = "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42
+                    8e5cade7615988c59c814ad058b432ca0eec606d774c5db045b9c3964601a457";
+    let expected: String = expected.split_whitespace().collect();
+    assert_eq!(hex::encode(pubkey.to_bytes_array()), expected);
+    assert_eq!(hex::encode(pubkey.to_bytes()), expected);
+}
+
+#[test]
+fn signature_layout() {
+    let (_, pubkey, sig) = signature();
+    // R || s (little-endian)
+    let expected = "30c8ffc79a1f5aa994e429be428daad3bd7c37286268c1ec2e100cb9d74ecb0f
+                    1b623e985ec8c46763de21bf17702e1d21e93714f003faa7f6788bc137959b0f";
+    let expected: String = expected.split_whitespace().collect();
+    assert_eq!(hex::encode(&sig.to_bytes()[..]), expected);
+
+    let mut transcript = Transcript::new(b"layout");
+    transcript.append_message(b"message", b"hello");
+    assert!(sig.verify(&mut transcript, pubkey).is_ok());
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "metrics")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{decompression_count, reset_decompression_count, Signature, VerificationKey};
+
+// The counter is global, so the checks share one test to keep them from running
+// concurrently and observing each other's decompressions.
+#[test]
+fn counts_decompressions() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+
+    reset_decompression_count();
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+    assert_eq!(decompression_count(), 1);
+
+    // `G`, `R` and `H` are decompressed once each.
+    assert!(sig
+        .verify(&mut Transcript::new(b"example transcript"), pubkey)
+        .is_ok());
+    assert_eq!(decompression_count(), 4);
+
+    reset_decompression_count();
+    assert_eq!(decompression_count(), 0);
+
+    // Prepared keys skip decompression: only `R` is decompressed.
+    let prepared = VerificationKey::from_secret_prepared(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign_prepared(&mut Transcript::new(b"example transcript"), &prepared, privkey);
+    assert!(sig
+        .verify_prepared(&mut Transcript::new(b"example transcript"), &prepared)
+        .is_ok());
+    assert_eq!(decompression_count(), 1);
+}
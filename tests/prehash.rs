@@ -0,0 +1,52 @@
+#![cfg(feature = "prehash")]
+
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha256, Sha512};
+use std::io::Write;
+use zkschnorr::{Hasher, Signature, VerificationKey};
+
+#[test]
+fn sign_large_input_in_chunks() {
+    let input: Vec<u8> = (0..5 * 1024 * 1024u32).map(|i| (i * 31 + 7) as u8).collect();
+
+    let mut hasher = Hasher::new();
+    for chunk in input.chunks(64 * 1024 + 3) {
+        hasher.update(chunk);
+    }
+    let digest = hasher.finalize();
+    assert_eq!(&digest[..], &Sha512::digest(&input)[..]);
+
+    let mut writer = Hasher::new();
+    std::io::copy(&mut &input[..], &mut writer).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.finalize(), digest);
+
+    let privkey = Scalar::from(9u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign_prehashed(b"file", &digest, pubkey, privkey);
+    assert!(sig.verify_prehashed(b"file", &digest, pubkey).is_ok());
+    assert!(sig.verify_prehashed(b"other", &digest, pubkey).is_err());
+
+    let mut tampered = digest;
+    tampered[0] ^= 1;
+    assert!(sig.verify_prehashed(b"file", &tampered, pubkey).is_err());
+    assert!(sig.verify_message(b"file", &digest, pubkey).is_err());
+}
+
+#[test]
+fn prehash_binds_algorithm() {
+    let privkey = Scalar::from(9u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let message = b"a large message";
+
+    let sig = Signature::sign_prehashed_with::<Sha256>(b"file", message, pubkey, privkey);
+    assert!(sig.verify_prehashed_with::<Sha256>(b"file", message, pubkey).is_ok());
+    assert!(sig.verify_prehashed_with::<Sha512>(b"file", message, pubkey).is_err());
+    assert!(sig.verify_prehashed_with::<Sha256>(b"other", message, pubkey).is_err());
+    assert!(sig.verify_prehashed_with::<Sha256>(b"file", b"another message", pubkey).is_err());
+
+    let sig = Signature::sign_prehashed_with::<Sha512>(b"file", message, pubkey, privkey);
+    let mut digest = [0u8; 64];
+    digest.copy_from_slice(&Sha512::digest(message));
+    assert!(sig.verify_prehashed(b"file", &digest, pubkey).is_err());
+}
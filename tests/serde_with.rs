@@ -0,0 +1,75 @@
+#![cfg(feature = "serde")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Bytes, DisplayFromStr};
+use zkschnorr::{Signature, VerificationKey};
+
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Envelope {
+    #[serde_as(as = "DisplayFromStr")]
+    signature: Signature,
+    #[serde_as(as = "DisplayFromStr")]
+    pubkey: VerificationKey,
+    #[serde_as(as = "Bytes")]
+    raw: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Plain {
+    signature: Signature,
+    pubkey: VerificationKey,
+}
+
+fn signed() -> (Signature, VerificationKey) {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"example transcript"), pubkey, privkey);
+    (sig, pubkey)
+}
+
+#[test]
+fn display_from_str_round_trip() {
+    let (signature, pubkey) = signed();
+    let envelope = Envelope {
+        signature,
+        pubkey,
+        raw: signature.to_bytes().to_vec(),
+    };
+
+    let json = serde_json::to_value(&envelope).unwrap();
+    assert_eq!(json["signature"], hex::encode(&signature.to_bytes()[..]));
+    assert_eq!(json["pubkey"], hex::encode(pubkey.to_bytes()));
+    assert_eq!(serde_json::from_value::<Envelope>(json).unwrap(), envelope);
+
+    assert_eq!(signature.to_string().parse::<Signature>().unwrap(), signature);
+    assert_eq!(pubkey.to_string().parse::<VerificationKey>().unwrap(), pubkey);
+    assert!("not hex".parse::<Signature>().is_err());
+    assert!("abcd".parse::<VerificationKey>().is_err());
+}
+
+#[test]
+fn human_readable_and_binary_forms() {
+    let (signature, pubkey) = signed();
+    let plain = Plain { signature, pubkey };
+
+    // Human-readable formats use the same hex strings as `Display`.
+    let json = serde_json::to_value(&plain).unwrap();
+    assert_eq!(json["signature"], signature.to_string());
+    assert_eq!(json["pubkey"], pubkey.to_string());
+    assert_eq!(serde_json::from_value::<Plain>(json).unwrap(), plain);
+
+    // Byte arrays are still accepted.
+    let json = serde_json::json!({
+        "signature": signature.to_bytes().to_vec(),
+        "pubkey": pubkey.to_bytes(),
+    });
+    assert_eq!(serde_json::from_value::<Plain>(json).unwrap(), plain);
+
+    // Binary formats use raw bytes.
+    let encoded = bincode::serialize(&plain).unwrap();
+    assert_eq!(encoded.len(), 2 * (8 + 64));
+    assert_eq!(bincode::deserialize::<Plain>(&encoded).unwrap(), plain);
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "dangerous-session-serde")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{SigningSession, VerificationKey};
+
+#[test]
+fn resume_serialized_session() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    // Pause after committing: only the persisted session survives.
+    let (saved, json, commitment) = {
+        let session = SigningSession::commit(&mut Transcript::new(b"session"), pubkey, &privkey);
+        (
+            bincode::serialize(&session).unwrap(),
+            serde_json::to_string(&session).unwrap(),
+            session.commitment(),
+        )
+    };
+
+    let resumed: SigningSession = bincode::deserialize(&saved).unwrap();
+    assert_eq!(resumed.commitment(), commitment);
+    let sig = resumed.finalize(&privkey);
+    assert!(sig
+        .verify_committed(&commitment, &mut Transcript::new(b"session"), pubkey)
+        .is_ok());
+
+    let resumed: SigningSession = serde_json::from_str(&json).unwrap();
+    assert_eq!(resumed.finalize(&privkey), sig);
+}
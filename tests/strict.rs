@@ -0,0 +1,41 @@
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{Signature, VerificationKey, ZkSchnorrError};
+
+#[test]
+fn construction_is_validated() {
+    let privkey = Scalar::from(11u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"strict"), pubkey, privkey);
+
+    // Outside the crate signatures are built from validated parts
+    // (see `tests/ui/signature_fields.rs`).
+    let rebuilt = Signature::from_parts(sig.R(), sig.s()).unwrap();
+    assert_eq!(rebuilt, sig);
+    assert!(rebuilt.verify(&mut Transcript::new(b"strict"), pubkey).is_ok());
+
+    let non_canonical = Scalar::from_bits([0xff; 32]);
+    assert_eq!(
+        Signature::from_parts(sig.R(), non_canonical),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+    assert_eq!(
+        Signature::from_parts(CompressedRistretto([0xff; 32]), sig.s()),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+
+    let mut bytes = sig.to_bytes();
+    bytes[32..].copy_from_slice(non_canonical.as_bytes());
+    assert_eq!(
+        Signature::from_bytes(&bytes[..]),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+
+    let mut bytes = sig.to_bytes();
+    bytes[..32].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Signature::from_bytes(&bytes[..]),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
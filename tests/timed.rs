@@ -0,0 +1,44 @@
+#![cfg(feature = "timed")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use std::time::Duration;
+use zkschnorr::{BatchVerifier, Signature, VerificationKey, ZkSchnorrError};
+
+#[test]
+fn verify_timed() {
+    let privkey = Scalar::from(3u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+    let sig = Signature::sign(&mut Transcript::new(b"timed"), pubkey, privkey);
+
+    let (result, elapsed) = sig.verify_timed(&mut Transcript::new(b"timed"), pubkey);
+    assert_eq!(result, Ok(()));
+    assert!(elapsed > Duration::default());
+
+    let (result, _) = sig.verify_timed(&mut Transcript::new(b"other"), pubkey);
+    assert_eq!(result, Err(ZkSchnorrError::InvalidSignature));
+
+    // A signature with an undecodable `R` cannot be decoded, let alone timed.
+    let mut bytes = sig.to_bytes();
+    bytes[..32].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        Signature::from_bytes(&bytes[..]),
+        Err(ZkSchnorrError::InvalidSignature)
+    );
+}
+
+#[test]
+fn batch_verify_timed() {
+    let mut batch = BatchVerifier::new(rand::thread_rng());
+    for i in 2..6u64 {
+        let privkey = Scalar::from(i);
+        let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(10987u64));
+        let sig = Signature::sign(&mut Transcript::new(b"timed"), pubkey, privkey);
+        batch
+            .append_signature(&mut Transcript::new(b"timed"), pubkey, &sig)
+            .unwrap();
+    }
+    let (result, elapsed) = batch.verify_timed();
+    assert_eq!(result, Ok(()));
+    assert!(elapsed > Duration::default());
+}
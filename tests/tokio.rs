@@ -0,0 +1,37 @@
+#![cfg(feature = "tokio")]
+
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use rand::rngs::OsRng;
+use zkschnorr::{BatchVerifier, Signature, VerificationKey, ZkSchnorrError};
+
+fn batch(count: u64, corrupt: bool) -> BatchVerifier<OsRng> {
+    let mut batch = BatchVerifier::new(OsRng);
+    for i in 0..count {
+        let privkey = Scalar::from(1000 + i);
+        let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(2000 + i));
+        let sig = Signature::sign(&mut Transcript::new(b"tokio"), pubkey, privkey);
+        let label: &'static [u8] = if corrupt && i == count / 2 { b"other" } else { b"tokio" };
+        sig.verify_batched(&mut Transcript::new(label), pubkey, &mut batch);
+    }
+    batch
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn verify_blocking_on_runtime() {
+    assert!(batch(32, false).verify_blocking().await.is_ok());
+    assert_eq!(
+        batch(32, true).verify_blocking().await,
+        Err(ZkSchnorrError::InvalidBatch)
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn verify_blocking_on_current_thread_runtime() {
+    let (good, bad) = tokio::join!(
+        batch(8, false).verify_blocking(),
+        batch(8, true).verify_blocking()
+    );
+    assert!(good.is_ok());
+    assert_eq!(bad, Err(ZkSchnorrError::InvalidBatch));
+}
@@ -0,0 +1,11 @@
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use zkschnorr::{SigningSession, VerificationKey};
+
+fn main() {
+    let privkey = Scalar::from(1u64);
+    let pubkey = VerificationKey::from_secret(&privkey, &Scalar::from(2u64));
+    let session = SigningSession::commit(&mut Transcript::new(b"ui"), pubkey, &privkey);
+    let copy = session.clone();
+    let _ = (session.finalize(&privkey), copy.finalize(&privkey));
+}
@@ -0,0 +1,11 @@
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use zkschnorr::Signature;
+
+fn main() {
+    let sig = Signature {
+        s: Scalar::one(),
+        R: CompressedRistretto::default(),
+    };
+    let _ = (sig.s, sig.R);
+}
@@ -0,0 +1,123 @@
+//! Reproducible test vectors.
+//!
+//! The vectors in `tests/vectors.json` (transcript API) and `tests/message_vectors.json`
+//! (message API, checked by `examples/verify_vectors.rs`) are generated by a `ChaCha20Rng`
+//! from a fixed seed, so they only change when the signature format or transcript layout
+//! changes. The RNG is named explicitly because `StdRng` may change its algorithm across
+//! `rand` releases. After an intentional format change, regenerate them with:
+//!
+//! ```text
+//! cargo test --test vectors_gen -- --ignored
+//! ```
+//!
+//! and commit the updated file together with the change.
+
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use rand_chacha::ChaCha20Rng;
+use rand::SeedableRng;
+use serde_json::{json, Value};
+use zkschnorr::{Signature, VerificationKey, MESSAGE_TRANSCRIPT_LABEL};
+
+const VECTORS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors.json");
+const MESSAGE_VECTORS_PATH: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/tests/message_vectors.json");
+const SEED: [u8; 32] = [7u8; 32];
+const LABEL: &[u8] = b"zkschnorr test vectors";
+
+fn generate() -> Value {
+    let mut rng = ChaCha20Rng::from_seed(SEED);
+    let vectors: Vec<Value> = (0..8u64)
+        .map(|i| {
+            let privkey = Scalar::random(&mut rng);
+            let r = Scalar::random(&mut rng);
+            let pubkey = VerificationKey::from_secret(&privkey, &r);
+            let message = format!("message {}", i);
+
+            let mut transcript = Transcript::new(LABEL);
+            transcript.append_message(b"message", message.as_bytes());
+            let sig = Signature::sign_with_rng(&mut transcript, pubkey, privkey, &mut rng);
+
+            json!({
+                "privkey": hex::encode(privkey.as_bytes()),
+                "pubkey": hex::encode(pubkey.to_bytes()),
+                "message": message,
+                "signature": hex::encode(&sig.to_bytes()[..]),
+            })
+        })
+        .collect();
+    json!({
+        "transcript_label": String::from_utf8(LABEL.to_vec()).unwrap(),
+        "message_label": "message",
+        "vectors": vectors,
+    })
+}
+
+/// Vectors for `Signature::sign_message` with the message label "message".
+fn generate_message_vectors() -> Value {
+    let mut rng = ChaCha20Rng::from_seed(SEED);
+    let vectors: Vec<Value> = (0..8u64)
+        .map(|i| {
+            let privkey = Scalar::random(&mut rng);
+            let r = Scalar::random(&mut rng);
+            let pubkey = VerificationKey::from_secret(&privkey, &r);
+            let message = format!("message {}", i);
+
+            let mut transcript = Transcript::new(MESSAGE_TRANSCRIPT_LABEL);
+            transcript.append_message(b"message", message.as_bytes());
+            let sig = Signature::sign_with_rng(&mut transcript, pubkey, privkey, &mut rng);
+            assert!(sig.verify_message(b"message", message.as_bytes(), pubkey).is_ok());
+
+            json!({
+                "privkey": hex::encode(privkey.as_bytes()),
+                "pubkey": hex::encode(pubkey.to_bytes()),
+                "message": message,
+                "signature": hex::encode(&sig.to_bytes()[..]),
+            })
+        })
+        .collect();
+    json!({
+        "transcript_label": String::from_utf8(MESSAGE_TRANSCRIPT_LABEL.to_vec()).unwrap(),
+        "message_label": "message",
+        "vectors": vectors,
+    })
+}
+
+#[test]
+#[ignore]
+fn regenerate_vectors() {
+    let contents = serde_json::to_string_pretty(&generate()).unwrap();
+    std::fs::write(VECTORS_PATH, contents + "\n").unwrap();
+    let contents = serde_json::to_string_pretty(&generate_message_vectors()).unwrap();
+    std::fs::write(MESSAGE_VECTORS_PATH, contents + "\n").unwrap();
+}
+
+#[test]
+fn pinned_message_vectors_match() {
+    let pinned: Value =
+        serde_json::from_str(&std::fs::read_to_string(MESSAGE_VECTORS_PATH).unwrap()).unwrap();
+    assert_eq!(pinned, generate_message_vectors());
+}
+
+#[test]
+fn pinned_vectors_match() {
+    let pinned: Value =
+        serde_json::from_str(&std::fs::read_to_string(VECTORS_PATH).unwrap()).unwrap();
+    assert_eq!(pinned, generate());
+
+    for v in pinned["vectors"].as_array().unwrap() {
+        let pubkey = hex::decode(v["pubkey"].as_str().unwrap()).unwrap();
+        let mut g = [0u8; 32];
+        let mut h = [0u8; 32];
+        g.copy_from_slice(&pubkey[..32]);
+        h.copy_from_slice(&pubkey[32..]);
+        let pubkey = VerificationKey::new(CompressedRistretto(g), CompressedRistretto(h));
+        let sig = Signature::from_bytes(&hex::decode(v["signature"].as_str().unwrap()).unwrap()[..])
+            .unwrap();
+
+        let mut transcript = Transcript::new(LABEL);
+        transcript.append_message(b"message", v["message"].as_str().unwrap().as_bytes());
+        assert!(sig.verify(&mut transcript, pubkey).is_ok());
+    }
+}
@@ -0,0 +1,59 @@
+#![deny(missing_docs)]
+//! Derive macro for `zkschnorr::Signable`.
+
+use proc_macro::TokenStream;
+use proc_macro2::Literal;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index};
+
+/// Derives `zkschnorr::Signable` for a struct.
+///
+/// The generated `to_transcript` appends the struct name labelled "type" and then
+/// every field in declaration order, labelled with the field name (or its index
+/// for tuple structs). Renaming or reordering fields therefore changes the
+/// transcript and invalidates existing signatures.
+#[proc_macro_derive(Signable)]
+pub fn derive_signable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let type_name = Literal::byte_string(name.to_string().as_bytes());
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new_spanned(&input, "Signable can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+    let appends = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                let label = Literal::byte_string(ident.to_string().as_bytes());
+                quote! { ::zkschnorr::SignableField::append_field(&self.#ident, #label, t); }
+            })
+            .collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => (0..fields.unnamed.len())
+            .map(|i| {
+                let index = Index::from(i);
+                let label = Literal::byte_string(i.to_string().as_bytes());
+                quote! { ::zkschnorr::SignableField::append_field(&self.#index, #label, t); }
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let expanded = quote! {
+        impl #impl_generics ::zkschnorr::Signable for #name #ty_generics #where_clause {
+            fn to_transcript(&self, t: &mut ::zkschnorr::__private::Transcript) {
+                t.append_message(b"type", #type_name);
+                #(#appends)*
+            }
+        }
+    };
+    expanded.into()
+}